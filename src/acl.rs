@@ -0,0 +1,178 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use clap::ValueEnum;
+use core::fmt;
+use zookeeper_async::{Acl, Permission};
+
+const PERMISSIONS: [(Permission, char); 5] = [
+    (Permission::CREATE, 'c'),
+    (Permission::DELETE, 'd'),
+    (Permission::READ, 'r'),
+    (Permission::WRITE, 'w'),
+    (Permission::ADMIN, 'a'),
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum AclPreset {
+    #[default]
+    Open,
+    ReadOnly,
+    CreatorAll,
+    Digest,
+}
+
+impl AclPreset {
+    const ALL: [AclPreset; 4] = [
+        AclPreset::Open,
+        AclPreset::ReadOnly,
+        AclPreset::CreatorAll,
+        AclPreset::Digest,
+    ];
+
+    fn position(&self) -> usize {
+        AclPreset::ALL
+            .iter()
+            .position(|p| p == self)
+            .unwrap_or_default()
+    }
+
+    pub fn next(self) -> Self {
+        AclPreset::ALL[(self.position() + 1) % AclPreset::ALL.len()]
+    }
+
+    pub fn previous(self) -> Self {
+        let len = AclPreset::ALL.len();
+        AclPreset::ALL[(self.position() + len - 1) % len]
+    }
+
+    pub fn requires_credentials(&self) -> bool {
+        matches!(self, AclPreset::Digest)
+    }
+
+    /// Builds the ACL list for the preset; `credentials` are `user:password` for the digest preset.
+    pub fn acl(&self, credentials: &str) -> Result<Vec<Acl>, String> {
+        match self {
+            AclPreset::Open => Ok(Acl::open_unsafe().clone()),
+            AclPreset::ReadOnly => Ok(Acl::read_unsafe().clone()),
+            AclPreset::CreatorAll => Ok(Acl::creator_all().clone()),
+            AclPreset::Digest => digest_id(credentials)
+                .map(|id| vec![Acl::new(Permission::ALL, "digest", id)])
+                .ok_or_else(|| "Digest credentials must be in user:password format".to_owned()),
+        }
+    }
+}
+
+impl fmt::Display for AclPreset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            AclPreset::Open => "Open (world:anyone:cdrwa)",
+            AclPreset::ReadOnly => "Read-Only (world:anyone:r)",
+            AclPreset::CreatorAll => "Creator All (auth::cdrwa)",
+            AclPreset::Digest => "Custom Digest",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Computes the `digest` scheme id `user:base64(sha1(user:password))`.
+pub fn digest_id(credentials: &str) -> Option<String> {
+    let (user, password) = credentials.split_once(':')?;
+    if user.is_empty() || password.is_empty() {
+        return None;
+    }
+    let hash = sha1_smol::Sha1::from(credentials.as_bytes())
+        .digest()
+        .bytes();
+    Some(format!("{user}:{}", STANDARD.encode(hash)))
+}
+
+pub fn perms_to_string(perms: Permission) -> String {
+    PERMISSIONS
+        .iter()
+        .map(|(perm, c)| if perms.can(*perm) { *c } else { '-' })
+        .collect()
+}
+
+pub fn parse_perms(perms: &str) -> Option<Permission> {
+    perms.chars().try_fold(Permission::NONE, |acc, c| {
+        PERMISSIONS
+            .iter()
+            .find(|(_, p)| *p == c)
+            .map(|(perm, _)| acc | *perm)
+    })
+}
+
+pub fn toggle_perm(perms: Permission, c: char) -> Permission {
+    PERMISSIONS
+        .iter()
+        .filter(|(perm, p)| perms.can(*perm) != (*p == c))
+        .fold(Permission::NONE, |acc, (perm, _)| acc | *perm)
+}
+
+/// Parses an ACL entry in `scheme:id:perms` format, e.g. `world:anyone:r` or `ip:10.0.0.1:cdrwa`.
+pub fn parse_acl_entry(entry: &str) -> Result<Acl, String> {
+    let err = || format!("Invalid ACL entry {entry}, expected scheme:id:perms");
+    let (scheme_id, perms) = entry.trim().rsplit_once(':').ok_or_else(err)?;
+    let (scheme, id) = scheme_id.split_once(':').ok_or_else(err)?;
+    let perms = parse_perms(perms).ok_or_else(err)?;
+    if scheme.is_empty() {
+        return Err(err());
+    }
+    Ok(Acl::new(perms, scheme, id))
+}
+
+pub fn acl_to_string(acl: &Acl) -> String {
+    format!("{}:{} {}", acl.scheme, acl.id, perms_to_string(acl.perms))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digest_id_matches_zookeeper() {
+        // DigestAuthenticationProvider.generateDigest("super:superpw")
+        assert_eq!(
+            digest_id("super:superpw").as_deref(),
+            Some("super:g9oN2HttPfn8MMWJZ2r45Np/LIA=")
+        );
+    }
+
+    #[test]
+    fn digest_id_requires_user_and_password() {
+        assert_eq!(digest_id("user"), None);
+        assert_eq!(digest_id(":password"), None);
+        assert_eq!(digest_id("user:"), None);
+    }
+
+    #[test]
+    fn parses_acl_entries() {
+        let acl = parse_acl_entry(" world:anyone:r ").unwrap();
+        assert_eq!((acl.scheme.as_str(), acl.id.as_str()), ("world", "anyone"));
+        assert_eq!(acl.perms, Permission::READ);
+
+        let acl = parse_acl_entry("ip:10.0.0.1:cdrwa").unwrap();
+        assert_eq!(acl.id, "10.0.0.1");
+        assert_eq!(acl.perms, Permission::ALL);
+
+        // digest ids contain a colon themselves
+        let acl = parse_acl_entry("digest:user:tpUq/4Pn5A64fVZyQ0gOJ8ZWqkY=:rw").unwrap();
+        assert_eq!(acl.id, "user:tpUq/4Pn5A64fVZyQ0gOJ8ZWqkY=");
+        assert_eq!(acl.perms, Permission::READ | Permission::WRITE);
+    }
+
+    #[test]
+    fn rejects_invalid_acl_entries() {
+        assert!(parse_acl_entry("world:anyone").is_err());
+        assert!(parse_acl_entry("world:anyone:rx").is_err());
+        assert!(parse_acl_entry(":anyone:r").is_err());
+        assert!(parse_acl_entry("anyone").is_err());
+    }
+
+    #[test]
+    fn toggles_permissions() {
+        let perms = parse_perms("rw").unwrap();
+        assert_eq!(perms_to_string(perms), "--rw-");
+        assert_eq!(perms_to_string(toggle_perm(perms, 'a')), "--rwa");
+        assert_eq!(perms_to_string(toggle_perm(perms, 'r')), "---w-");
+    }
+}
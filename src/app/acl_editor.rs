@@ -0,0 +1,136 @@
+use super::{state::TabState, App};
+use crate::{
+    acl::{parse_acl_entry, toggle_perm},
+    errors::zk_error_message,
+};
+use std::mem;
+use zookeeper_async::ZkError;
+
+impl App {
+    pub(crate) async fn store_node_acl(&mut self) {
+        let Some(ref zk) = self.cluster().zk else {
+            return;
+        };
+
+        let path = self.tab_path();
+        let acl = match zk.get_acl(&path).await {
+            Ok((acl, _)) => Some(acl),
+            Err(e) => {
+                self.set_tab_message(zk_error_message("read ACL of", &path, &e));
+                None
+            }
+        };
+        self.curr_tab_mut().current_node_acl = acl;
+    }
+
+    pub(crate) async fn open_acl_editor(&mut self) {
+        let Some(ref zk) = self.cluster().zk else {
+            "Failed to get zookeeper client".clone_into(&mut self.curr_tab_mut().message);
+            return;
+        };
+
+        match zk.get_acl(&self.tab_path()).await {
+            Ok((acl, stat)) => {
+                let tab = self.curr_tab_mut();
+                tab.acl_buf = acl;
+                tab.acl_version = stat.aversion;
+                tab.acl_list_state.select(Some(0));
+                tab.state = TabState::EditAcl;
+            }
+            Err(e) => self.set_tab_message(zk_error_message("read ACL of", &self.tab_path(), &e)),
+        }
+    }
+
+    pub(crate) fn next_acl_entry(&mut self) {
+        let len = self.curr_tab().acl_buf.len();
+        let i = match self.curr_tab().acl_list_state.selected() {
+            Some(i) if i + 1 < len => i + 1,
+            _ => 0,
+        };
+        self.curr_tab_mut().acl_list_state.select(Some(i));
+    }
+
+    pub(crate) fn previous_acl_entry(&mut self) {
+        let len = self.curr_tab().acl_buf.len();
+        let i = match self.curr_tab().acl_list_state.selected() {
+            Some(0) | None => len.saturating_sub(1),
+            Some(i) => i - 1,
+        };
+        self.curr_tab_mut().acl_list_state.select(Some(i));
+    }
+
+    pub(crate) fn toggle_acl_perm(&mut self, perm: char) {
+        let tab = self.curr_tab_mut();
+        let Some(acl) = tab
+            .acl_list_state
+            .selected()
+            .and_then(|i| tab.acl_buf.get_mut(i))
+        else {
+            return;
+        };
+        acl.perms = toggle_perm(acl.perms, perm);
+    }
+
+    pub(crate) fn remove_acl_entry(&mut self) {
+        let tab = self.curr_tab_mut();
+        if let Some(i) = tab.acl_list_state.selected() {
+            if i < tab.acl_buf.len() {
+                tab.acl_buf.remove(i);
+            }
+        }
+        let last = tab.acl_buf.len().saturating_sub(1);
+        let selected = tab.acl_list_state.selected().map(|i| i.min(last));
+        tab.acl_list_state.select(selected);
+    }
+
+    pub(crate) fn add_acl_entry(&mut self) {
+        let entry = mem::take(&mut self.curr_tab_mut().input_buf);
+        match parse_acl_entry(&entry) {
+            Ok(acl) => {
+                let tab = self.curr_tab_mut();
+                tab.acl_buf.push(acl);
+                tab.acl_list_state.select(Some(tab.acl_buf.len() - 1));
+                tab.state = TabState::EditAcl;
+            }
+            Err(e) => self.set_tab_message(e),
+        }
+    }
+
+    pub(crate) async fn save_acl(&mut self) {
+        if !self.ensure_can_write() {
+            return;
+        }
+        let Some(ref zk) = self.cluster().zk else {
+            "Failed to get zookeeper client".clone_into(&mut self.curr_tab_mut().message);
+            return;
+        };
+        if self.curr_tab().acl_buf.is_empty() {
+            self.set_tab_message("ACL must contain at least one entry".to_owned());
+            return;
+        }
+
+        let path = self.tab_path();
+        let res = zk
+            .set_acl(
+                &path,
+                self.curr_tab().acl_buf.clone(),
+                Some(self.curr_tab().acl_version),
+            )
+            .await;
+        match res {
+            Ok(stat) => {
+                self.curr_tab_mut().acl_version = stat.aversion;
+                self.curr_tab_mut().state = TabState::Tab;
+                self.set_tab_message(format!("Node {path} ACL successfully updated"));
+                self.store_node_acl().await;
+            }
+            Err(ZkError::BadVersion) => self.set_tab_message(format!(
+                "Node {path} ACL was changed by another client, reopen the editor"
+            )),
+            Err(ZkError::NoAuth) => {
+                self.set_tab_message(zk_error_message("update ACL of", &path, &ZkError::NoAuth))
+            }
+            Err(e) => self.set_tab_message(format!("ACL update failed : {e}")),
+        }
+    }
+}
@@ -0,0 +1,738 @@
+use super::{
+    keymap::KeyContext,
+    search::SearchKind,
+    state::{AppState, TabState},
+    App,
+};
+use core::fmt;
+use crossterm::event::{KeyCode, KeyEvent};
+use std::ops::ControlFlow;
+
+/// What a key does, the meaning of the common actions depends on the current screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Quit,
+    Back,    // one step back, closes the form or the popup
+    Close,   // closes the popup down to the nodes list
+    Confirm, // submits the form or opens the selected item
+    NextField,
+    MoveDown,
+    MoveUp,
+    Insert(char), // typed into the focused input
+    DeleteChar,
+    Remove,        // removes the selected profile, bookmark or ACL entry
+    Select(usize), // switches to the tab or opens the bookmark with the index
+    // connection screen
+    EditConnection,
+    EditAuth,
+    SaveProfile,
+    // nodes list
+    ToggleStats,
+    ToggleTree,
+    Filter,
+    SearchNames,
+    SearchData,
+    GoToPath,
+    HistoryBack,
+    HistoryForward,
+    AddBookmark,
+    Bookmarks,
+    NewTab,
+    DuplicateTab,
+    CloseTab,
+    RenameTab,
+    NextTab,
+    PreviousTab,
+    OpenClusters,
+    ToggleSplit,
+    FocusOtherPane,
+    CopyToOtherPane,
+    ReadData,
+    CreateNode,
+    EditAcl,
+    Export,
+    Diff,
+    Import,
+    DeleteNode,
+    // node data
+    ShowString,
+    ShowJson,
+    ShowRaw,
+    EditData,
+    // ACL editor
+    NewEntry,
+    TogglePermission(char),
+}
+
+/// Name of the action in the key bindings of the config file.
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Action::Quit => "quit",
+            Action::Back => "back",
+            Action::Close => "close",
+            Action::Confirm => "confirm",
+            Action::NextField => "next-field",
+            Action::MoveDown => "move-down",
+            Action::MoveUp => "move-up",
+            Action::Insert(value) => return write!(f, "insert-{value}"),
+            Action::DeleteChar => "delete-char",
+            Action::Remove => "remove",
+            Action::Select(index) => return write!(f, "select-{}", index + 1),
+            Action::EditConnection => "edit-connection",
+            Action::EditAuth => "edit-auth",
+            Action::SaveProfile => "save-profile",
+            Action::ToggleStats => "toggle-stats",
+            Action::ToggleTree => "toggle-tree",
+            Action::Filter => "filter",
+            Action::SearchNames => "search-names",
+            Action::SearchData => "search-data",
+            Action::GoToPath => "go-to-path",
+            Action::HistoryBack => "history-back",
+            Action::HistoryForward => "history-forward",
+            Action::AddBookmark => "add-bookmark",
+            Action::Bookmarks => "bookmarks",
+            Action::NewTab => "new-tab",
+            Action::DuplicateTab => "duplicate-tab",
+            Action::CloseTab => "close-tab",
+            Action::RenameTab => "rename-tab",
+            Action::NextTab => "next-tab",
+            Action::PreviousTab => "previous-tab",
+            Action::OpenClusters => "open-clusters",
+            Action::ToggleSplit => "toggle-split",
+            Action::FocusOtherPane => "focus-other-pane",
+            Action::CopyToOtherPane => "copy-to-other-pane",
+            Action::ReadData => "read-data",
+            Action::CreateNode => "create-node",
+            Action::EditAcl => "edit-acl",
+            Action::Export => "export",
+            Action::Diff => "diff",
+            Action::Import => "import",
+            Action::DeleteNode => "delete-node",
+            Action::ShowString => "show-string",
+            Action::ShowJson => "show-json",
+            Action::ShowRaw => "show-raw",
+            Action::EditData => "edit-data",
+            Action::NewEntry => "new-entry",
+            Action::TogglePermission(perm) => match perm {
+                'c' => "toggle-create",
+                'd' => "toggle-delete",
+                'r' => "toggle-read",
+                'w' => "toggle-write",
+                _ => "toggle-admin",
+            },
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl App {
+    /// Runs the action bound to the key on the current screen, text inputs take unbound characters.
+    pub async fn handle_key(&mut self, key: KeyEvent) -> ControlFlow<()> {
+        let context = self.key_context();
+        match (self.keymap.action(context, key.code), key.code) {
+            (Some(action), _) => self.dispatch(action).await,
+            (None, KeyCode::Char(value)) if context == KeyContext::Input => {
+                self.dispatch(Action::Insert(value)).await
+            }
+            _ => ControlFlow::Continue(()),
+        }
+    }
+
+    /// Applies `action` to the current screen, breaks when the app has to quit.
+    pub async fn dispatch(&mut self, action: Action) -> ControlFlow<()> {
+        match action {
+            Action::Quit => return ControlFlow::Break(()),
+            Action::Insert(value) => self.insert_char(value),
+            Action::DeleteChar => self.delete_char(),
+            _ if self.state == AppState::Tab => {
+                let state = self.curr_tab().state;
+                self.dispatch_tab(state, action).await;
+            }
+            _ => self.dispatch_connection(action).await,
+        }
+        ControlFlow::Continue(())
+    }
+
+    async fn dispatch_connection(&mut self, action: Action) {
+        match (self.state, action) {
+            (AppState::EstablishingConnection | AppState::ConnectionFailed, Action::Confirm) => {
+                self.connect().await
+            }
+            (
+                AppState::EstablishingConnection | AppState::ConnectionFailed,
+                Action::EditConnection,
+            ) => self.state = AppState::EditingConnection,
+            (AppState::EstablishingConnection | AppState::ConnectionFailed, Action::EditAuth) => {
+                self.state = AppState::EditingAuth
+            }
+            (AppState::EstablishingConnection, Action::MoveDown) => self.next_profile(),
+            (AppState::EstablishingConnection, Action::MoveUp) => self.previous_profile(),
+            (AppState::EstablishingConnection, Action::Remove) => self.confirm_delete_profile(),
+            (AppState::ConfirmDeleteProfile, Action::Confirm) => self.delete_profile(),
+            (AppState::EstablishingConnection, Action::SaveProfile) => {
+                self.profile_name_input = self
+                    .selected_profile()
+                    .map(|profile| profile.name.clone())
+                    .unwrap_or_default();
+                self.state = AppState::EditingProfileName;
+            }
+            (AppState::EditingConnection, Action::Confirm) => self.submit_connection_input(),
+            (AppState::EditingProfileName, Action::Confirm) => self.save_profile(),
+            (AppState::EditingProfileName, Action::Back) => {
+                self.profile_name_input.clear();
+                self.state = AppState::EstablishingConnection;
+            }
+            (
+                AppState::EditingConnection
+                | AppState::ConnectionFailed
+                | AppState::ConfirmDeleteProfile,
+                Action::Back,
+            )
+            | (AppState::EditingAuth, Action::Back | Action::Confirm) => {
+                self.state = AppState::EstablishingConnection
+            }
+            _ => {}
+        }
+    }
+
+    async fn dispatch_tab(&mut self, state: TabState, action: Action) {
+        match (state, action) {
+            (TabState::Tab, _) => self.dispatch_nodes(action).await,
+            (TabState::ReadNodeData, _) => self.dispatch_node_data(action),
+            (
+                TabState::EditCreateNodePath
+                | TabState::EditCreateNodeData
+                | TabState::EditCreateNodeMode
+                | TabState::EditCreateNodeTtl
+                | TabState::EditCreateNodeAcl
+                | TabState::EditCreateNodeCredentials,
+                _,
+            ) => self.dispatch_create_node(state, action).await,
+            (TabState::EditNodeData, Action::Back) => self.open_node_data().await,
+            (TabState::EditNodeData, Action::Confirm) => {
+                self.set_data().await;
+                self.open_node_data().await;
+            }
+            (
+                TabState::ExportSubtree
+                | TabState::ImportSnapshot
+                | TabState::EditDiffLeft
+                | TabState::EditDiffRight
+                | TabState::EditDiffConnection
+                | TabState::EditDiffAuth
+                | TabState::SearchInput
+                | TabState::GoToPath
+                | TabState::RenameTab
+                | TabState::EditBookmarkName,
+                Action::Back,
+            )
+            | (TabState::SearchResults, Action::Close) => self.close_input(),
+            (TabState::ExportSubtree, Action::Confirm) => self.export_subtree().await,
+            (TabState::ImportSnapshot, Action::Confirm) => self.plan_import().await,
+            (TabState::ImportSnapshot, Action::NextField) => self.next_import_strategy(),
+            (TabState::ImportPreview | TabState::CopyPreview, Action::Confirm) => {
+                self.apply_import().await
+            }
+            (TabState::ImportPreview | TabState::CopyPreview, Action::Back) => {
+                let tab = self.curr_tab_mut();
+                tab.import_plan = None;
+                tab.state = if state == TabState::ImportPreview {
+                    TabState::ImportSnapshot
+                } else {
+                    TabState::Tab
+                };
+            }
+            (
+                TabState::EditDiffLeft
+                | TabState::EditDiffRight
+                | TabState::EditDiffConnection
+                | TabState::EditDiffAuth,
+                Action::Confirm,
+            ) => self.compare_subtrees().await,
+            (
+                TabState::EditDiffLeft
+                | TabState::EditDiffRight
+                | TabState::EditDiffConnection
+                | TabState::EditDiffAuth,
+                Action::NextField,
+            ) => self.next_diff_field(),
+            (TabState::DiffView, Action::Back) => {
+                self.curr_tab_mut().diff = None;
+                self.curr_tab_mut().state = TabState::EditDiffLeft;
+            }
+            (TabState::DiffView, Action::Close) => {
+                self.curr_tab_mut().diff = None;
+                self.close_input();
+            }
+            (TabState::DiffView, Action::MoveDown) => self.scroll_diff_down(),
+            (TabState::DiffView, Action::MoveUp) => self.scroll_diff_up(),
+            (TabState::FilterNodes, Action::Back) => self.clear_filter(),
+            (TabState::FilterNodes, Action::Confirm) => self.submit_filter(),
+            (TabState::FilterNodes, Action::NextField) => {
+                self.next_pattern_kind();
+                self.apply_filter();
+            }
+            (TabState::FilterNodes, Action::MoveDown) => self.next(),
+            (TabState::FilterNodes, Action::MoveUp) => self.previous(),
+            (TabState::SearchInput, Action::Confirm) => self.run_search().await,
+            (TabState::SearchInput, Action::NextField) => self.next_pattern_kind(),
+            (TabState::SearchResults, Action::Back) => {
+                self.curr_tab_mut().state = TabState::SearchInput
+            }
+            (TabState::SearchResults, Action::MoveDown) => self.next_search_result(),
+            (TabState::SearchResults, Action::MoveUp) => self.previous_search_result(),
+            (TabState::SearchResults, Action::Confirm) => self.open_search_result().await,
+            (TabState::GoToPath, Action::Confirm) => self.go_to_path().await,
+            (TabState::GoToPath, Action::NextField) => self.complete_path().await,
+            (TabState::RenameTab, Action::Confirm) => self.rename_tab(),
+            (TabState::EditBookmarkName, Action::Confirm) => self.save_bookmark(),
+            (TabState::Clusters | TabState::Bookmarks, Action::Back | Action::Close) => {
+                self.curr_tab_mut().state = TabState::Tab
+            }
+            (TabState::Clusters, Action::MoveDown) => self.next_cluster(),
+            (TabState::Clusters, Action::MoveUp) => self.previous_cluster(),
+            (TabState::Clusters, Action::Confirm) => self.open_cluster_tab().await,
+            (TabState::Bookmarks, Action::MoveDown) => self.next_bookmark(),
+            (TabState::Bookmarks, Action::MoveUp) => self.previous_bookmark(),
+            (TabState::Bookmarks, Action::Remove) => self.delete_bookmark(),
+            (TabState::Bookmarks, Action::Confirm) => self.open_bookmark(None).await,
+            (TabState::Bookmarks, Action::Select(index)) => self.open_bookmark(Some(index)).await,
+            (TabState::DeleteNode, Action::Back) => self.curr_tab_mut().state = TabState::Tab,
+            (TabState::DeleteNode, Action::Confirm) => {
+                let planned =
+                    !self.curr_tab().recursive_delete || self.plan_recursive_delete().await;
+                if planned {
+                    self.curr_tab_mut().state = TabState::ConfirmDelete;
+                }
+            }
+            (TabState::DeleteNode, Action::NextField) => {
+                let curr = self.curr_tab().recursive_delete;
+                self.curr_tab_mut().recursive_delete = !curr;
+            }
+            (TabState::ConfirmDelete, Action::Back) => {
+                self.curr_tab_mut().delete_plan = None;
+                self.curr_tab_mut().state = TabState::DeleteNode;
+            }
+            (TabState::ConfirmDelete, Action::Confirm) => {
+                if !self.is_deletion_confirmed() {
+                    self.set_tab_message("Incorrect confirmation string".to_owned());
+                } else if self.curr_tab().recursive_delete {
+                    self.curr_tab_mut().state = TabState::DeletingNodes;
+                } else {
+                    self.delete_node().await;
+                    self.curr_tab_mut().state = TabState::Tab;
+                    self.reload_listing().await;
+                }
+            }
+            (TabState::DeletingNodes, Action::Back) => self.finish_recursive_delete().await,
+            (TabState::EditAcl, Action::Back) => self.curr_tab_mut().state = TabState::Tab,
+            (TabState::EditAcl, Action::Confirm) => self.save_acl().await,
+            (TabState::EditAcl, Action::MoveDown) => self.next_acl_entry(),
+            (TabState::EditAcl, Action::MoveUp) => self.previous_acl_entry(),
+            (TabState::EditAcl, Action::NewEntry) => {
+                self.curr_tab_mut().input_buf.clear();
+                self.curr_tab_mut().state = TabState::EditAclEntry;
+            }
+            (TabState::EditAcl, Action::Remove) => self.remove_acl_entry(),
+            (TabState::EditAcl, Action::TogglePermission(perm)) => self.toggle_acl_perm(perm),
+            (TabState::EditAclEntry, Action::Back) => self.curr_tab_mut().state = TabState::EditAcl,
+            (TabState::EditAclEntry, Action::Confirm) => self.add_acl_entry(),
+            _ => {}
+        }
+    }
+
+    async fn dispatch_nodes(&mut self, action: Action) {
+        let tree_mode = self.curr_tab().tree_mode;
+        match action {
+            Action::MoveDown => {
+                if tree_mode {
+                    self.tree_next();
+                } else {
+                    self.next();
+                    self.sync_list_selection();
+                }
+                self.auto_load_stat().await;
+            }
+            Action::MoveUp => {
+                if tree_mode {
+                    self.tree_previous();
+                } else {
+                    self.previous();
+                    self.sync_list_selection();
+                }
+                self.auto_load_stat().await;
+            }
+            Action::Confirm => {
+                if tree_mode {
+                    self.toggle_tree_node().await;
+                } else {
+                    self.enter_node().await;
+                }
+            }
+            Action::Back => {
+                if tree_mode {
+                    self.collapse_tree_node();
+                    self.auto_load_stat().await;
+                } else {
+                    self.leave_node().await;
+                }
+            }
+            Action::ToggleStats => {
+                let curr = self.curr_tab().toggle_stats_auto_load;
+                self.curr_tab_mut().toggle_stats_auto_load = !curr;
+                if !curr {
+                    self.store_node_stat().await;
+                } else {
+                    self.curr_tab_mut().current_node_stat = None;
+                    self.curr_tab_mut().current_node_acl = None;
+                }
+            }
+            Action::ToggleTree => self.toggle_tree_mode().await,
+            Action::Filter if !tree_mode => self.open_filter(),
+            Action::SearchNames => self.open_search(SearchKind::Names),
+            Action::SearchData => self.open_search(SearchKind::Data),
+            Action::GoToPath => self.open_go_to_path(),
+            Action::HistoryBack => self.history_back().await,
+            Action::HistoryForward => self.history_forward().await,
+            Action::AddBookmark => self.open_bookmark_name(),
+            Action::Bookmarks => self.open_bookmarks(),
+            Action::NewTab => self.new_tab().await,
+            Action::DuplicateTab => self.duplicate_tab().await,
+            Action::CloseTab => self.close_tab().await,
+            Action::RenameTab => self.open_rename_tab(),
+            Action::NextTab => self.next_tab().await,
+            Action::PreviousTab => self.previous_tab().await,
+            Action::Select(index) => self.switch_tab(index).await,
+            Action::OpenClusters => self.open_clusters(),
+            Action::ToggleSplit => self.toggle_split().await,
+            Action::FocusOtherPane => self.focus_other_pane().await,
+            Action::CopyToOtherPane => self.copy_to_other_pane().await,
+            Action::ReadData => self.open_node_data().await,
+            Action::CreateNode if self.ensure_can_write() => {
+                self.curr_tab_mut().node_path_buf = self.tab_path().to_string();
+                self.curr_tab_mut().state = TabState::EditCreateNodePath;
+            }
+            Action::EditAcl if self.ensure_can_write() => {
+                self.open_acl_editor().await;
+            }
+            Action::Export => self.open_export(),
+            Action::Diff => self.open_diff(),
+            Action::Import if self.ensure_can_write() => {
+                self.open_import();
+            }
+            Action::DeleteNode if self.ensure_can_delete() => {
+                self.curr_tab_mut().node_path_buf = self.tab_path().to_string();
+                self.curr_tab_mut().state = TabState::DeleteNode;
+            }
+            _ => {}
+        }
+    }
+
+    fn dispatch_node_data(&mut self, action: Action) {
+        let data = self.curr_tab().node_data.clone();
+        match action {
+            Action::Back => self.curr_tab_mut().state = TabState::Tab,
+            Action::ShowString => self.curr_tab_mut().node_data = data.convert_to_string(),
+            Action::ShowJson => self.curr_tab_mut().node_data = data.convert_to_json(),
+            Action::ShowRaw => self.curr_tab_mut().node_data = data.convert_to_raw(),
+            Action::EditData if self.ensure_can_write() => {
+                let tab = self.curr_tab_mut();
+                tab.node_data = data.convert_to_string();
+                tab.node_data_buf = tab.node_data.to_string();
+                tab.state = TabState::EditNodeData;
+            }
+            _ => {}
+        }
+    }
+
+    /// Moves through the fields of the create node form, Tab skips the fields the choices make unused.
+    async fn dispatch_create_node(&mut self, state: TabState, action: Action) {
+        let tab = self.curr_tab_mut();
+        match (state, action) {
+            (_, Action::Back) => tab.state = TabState::Tab,
+            (_, Action::Confirm) => self.create_node().await,
+            (TabState::EditCreateNodeMode, Action::MoveDown) => {
+                tab.create_mode = tab.create_mode.next()
+            }
+            (TabState::EditCreateNodeMode, Action::MoveUp) => {
+                tab.create_mode = tab.create_mode.previous()
+            }
+            (TabState::EditCreateNodeAcl, Action::MoveDown) => {
+                tab.acl_preset = tab.acl_preset.next()
+            }
+            (TabState::EditCreateNodeAcl, Action::MoveUp) => {
+                tab.acl_preset = tab.acl_preset.previous()
+            }
+            (_, Action::NextField) => {
+                tab.state = match state {
+                    TabState::EditCreateNodePath => TabState::EditCreateNodeData,
+                    TabState::EditCreateNodeData => TabState::EditCreateNodeMode,
+                    TabState::EditCreateNodeMode if tab.create_mode.requires_ttl() => {
+                        TabState::EditCreateNodeTtl
+                    }
+                    TabState::EditCreateNodeMode | TabState::EditCreateNodeTtl => {
+                        TabState::EditCreateNodeAcl
+                    }
+                    TabState::EditCreateNodeAcl if tab.acl_preset.requires_credentials() => {
+                        TabState::EditCreateNodeCredentials
+                    }
+                    _ => TabState::EditCreateNodePath,
+                }
+            }
+            _ => {}
+        }
+    }
+
+    async fn open_node_data(&mut self) {
+        self.curr_tab_mut().state = TabState::ReadNodeData;
+        self.store_node_data().await;
+    }
+
+    /// Leaves a text input for the nodes list, dropping what was typed.
+    fn close_input(&mut self) {
+        let tab = self.curr_tab_mut();
+        tab.input_buf.clear();
+        tab.state = TabState::Tab;
+    }
+
+    /// Text edited on the current screen.
+    fn input_mut(&mut self) -> Option<&mut String> {
+        let input = match self.state {
+            AppState::EditingConnection => &mut self.connection_input,
+            AppState::EditingAuth => &mut self.auth_input,
+            AppState::EditingProfileName => &mut self.profile_name_input,
+            AppState::Tab => match self.curr_tab().state {
+                TabState::EditDiffLeft
+                | TabState::EditDiffRight
+                | TabState::EditDiffConnection
+                | TabState::EditDiffAuth => self.diff_field_mut(),
+                TabState::EditCreateNodePath | TabState::DeleteNode => {
+                    &mut self.curr_tab_mut().node_path_buf
+                }
+                TabState::EditCreateNodeData | TabState::EditNodeData => {
+                    &mut self.curr_tab_mut().node_data_buf
+                }
+                TabState::EditCreateNodeTtl => &mut self.curr_tab_mut().ttl_buf,
+                TabState::EditCreateNodeCredentials => &mut self.curr_tab_mut().acl_credentials_buf,
+                TabState::FilterNodes => &mut self.curr_tab_mut().filter_buf,
+                TabState::ExportSubtree
+                | TabState::ImportSnapshot
+                | TabState::SearchInput
+                | TabState::GoToPath
+                | TabState::RenameTab
+                | TabState::EditBookmarkName
+                | TabState::ConfirmDelete
+                | TabState::EditAclEntry => &mut self.curr_tab_mut().input_buf,
+                _ => return None,
+            },
+            _ => return None,
+        };
+        Some(input)
+    }
+
+    fn insert_char(&mut self, value: char) {
+        let ttl =
+            self.state == AppState::Tab && self.curr_tab().state == TabState::EditCreateNodeTtl;
+        if ttl && !value.is_ascii_digit() {
+            return;
+        }
+        if let Some(input) = self.input_mut() {
+            input.push(value);
+            self.refilter();
+        }
+    }
+
+    fn delete_char(&mut self) {
+        if let Some(input) = self.input_mut() {
+            input.pop();
+            self.refilter();
+        }
+    }
+
+    /// The filter follows its input as it is typed.
+    fn refilter(&mut self) {
+        if self.state == AppState::Tab && self.curr_tab().state == TabState::FilterNodes {
+            self.apply_filter();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        acl::AclPreset,
+        app::cluster::Cluster,
+        create_mode::NodeCreateMode,
+        diff::{DiffEntry, DiffKind, SubtreeDiff},
+        tab::Tab,
+        zk_path::ZkPath,
+    };
+
+    /// App on the nodes list without a session, the stats are not loaded as they need one.
+    fn app(children: &[&str]) -> App {
+        let mut tab = Tab {
+            tab_data: children.iter().map(|child| child.to_string()).collect(),
+            toggle_stats_auto_load: false,
+            ..Default::default()
+        };
+        tab.list_state.select(Some(0));
+        App {
+            state: AppState::Tab,
+            tabs: vec![tab],
+            clusters: vec![Cluster::default()],
+            ..Default::default()
+        }
+    }
+
+    async fn dispatch_all(app: &mut App, actions: &[Action]) {
+        for action in actions {
+            assert_eq!(app.dispatch(*action).await, ControlFlow::Continue(()));
+        }
+    }
+
+    #[tokio::test]
+    async fn moves_through_the_list_and_wraps() {
+        let mut app = app(&["a", "b", "c"]);
+        dispatch_all(&mut app, &[Action::MoveDown, Action::MoveDown]).await;
+        assert_eq!(app.tab_path(), ZkPath::parse("/c").unwrap());
+        dispatch_all(&mut app, &[Action::MoveDown]).await;
+        assert_eq!(app.curr_tab().list_state.selected(), Some(0));
+        assert_eq!(app.tab_path(), ZkPath::parse("/a").unwrap());
+        dispatch_all(&mut app, &[Action::MoveUp]).await;
+        assert_eq!(app.curr_tab().list_state.selected(), Some(2));
+        assert_eq!(app.tab_path(), ZkPath::parse("/c").unwrap());
+    }
+
+    #[tokio::test]
+    async fn edits_the_focused_input() {
+        let mut app = app(&[]);
+        dispatch_all(&mut app, &[Action::GoToPath, Action::Insert('a')]).await;
+        assert_eq!(app.curr_tab().state, TabState::GoToPath);
+        assert_eq!(app.curr_tab().input_buf, "/a");
+        dispatch_all(&mut app, &[Action::DeleteChar, Action::DeleteChar]).await;
+        assert_eq!(app.curr_tab().input_buf, "");
+
+        app.curr_tab_mut().state = TabState::EditCreateNodeTtl;
+        dispatch_all(&mut app, &[Action::Insert('x'), Action::Insert('5')]).await;
+        assert_eq!(app.curr_tab().ttl_buf, "5");
+
+        app.state = AppState::EditingAuth;
+        dispatch_all(&mut app, &[Action::Insert('u'), Action::Insert(':')]).await;
+        assert_eq!(app.auth_input, "u:");
+        assert_eq!(app.curr_tab().ttl_buf, "5");
+    }
+
+    #[tokio::test]
+    async fn filters_and_restores_the_list() {
+        let mut app = app(&["alpha", "beta", "alpine"]);
+        dispatch_all(&mut app, &[Action::Filter]).await;
+        assert_eq!(app.curr_tab().state, TabState::FilterNodes);
+        dispatch_all(&mut app, &[Action::Insert('l'), Action::Insert('p')]).await;
+        assert_eq!(app.curr_tab().tab_data, ["alpha", "alpine"]);
+        dispatch_all(&mut app, &[Action::MoveDown]).await;
+        assert_eq!(app.curr_tab().list_state.selected(), Some(1));
+
+        dispatch_all(&mut app, &[Action::Back]).await;
+        let tab = app.curr_tab();
+        assert_eq!(tab.state, TabState::Tab);
+        assert_eq!(tab.tab_data, ["alpha", "beta", "alpine"]);
+        assert!(tab.filter_buf.is_empty());
+        assert!(tab.unfiltered_data.is_none());
+    }
+
+    #[tokio::test]
+    async fn cycles_the_create_node_fields_in_use() {
+        let mut app = app(&[]);
+        dispatch_all(&mut app, &[Action::CreateNode]).await;
+        let mut states = vec![app.curr_tab().state];
+        for _ in 0..4 {
+            dispatch_all(&mut app, &[Action::NextField]).await;
+            states.push(app.curr_tab().state);
+        }
+        assert_eq!(
+            states,
+            [
+                TabState::EditCreateNodePath,
+                TabState::EditCreateNodeData,
+                TabState::EditCreateNodeMode,
+                TabState::EditCreateNodeAcl,
+                TabState::EditCreateNodePath,
+            ]
+        );
+
+        let tab = app.curr_tab_mut();
+        tab.create_mode = NodeCreateMode::PersistentWithTtl;
+        tab.acl_preset = AclPreset::Digest;
+        let mut states = vec![app.curr_tab().state];
+        for _ in 0..6 {
+            dispatch_all(&mut app, &[Action::NextField]).await;
+            states.push(app.curr_tab().state);
+        }
+        assert_eq!(
+            states,
+            [
+                TabState::EditCreateNodePath,
+                TabState::EditCreateNodeData,
+                TabState::EditCreateNodeMode,
+                TabState::EditCreateNodeTtl,
+                TabState::EditCreateNodeAcl,
+                TabState::EditCreateNodeCredentials,
+                TabState::EditCreateNodePath,
+            ]
+        );
+
+        dispatch_all(&mut app, &[Action::Back]).await;
+        assert_eq!(app.curr_tab().state, TabState::Tab);
+    }
+
+    #[tokio::test]
+    async fn switches_tabs_and_wraps() {
+        let mut app = app(&[]);
+        app.tabs.extend([Tab::default(), Tab::default()]);
+        dispatch_all(&mut app, &[Action::PreviousTab]).await;
+        assert_eq!(app.curr_tab, 2);
+        dispatch_all(&mut app, &[Action::NextTab]).await;
+        assert_eq!(app.curr_tab, 0);
+        dispatch_all(&mut app, &[Action::Select(1)]).await;
+        assert_eq!(app.curr_tab, 1);
+        dispatch_all(&mut app, &[Action::Select(5)]).await;
+        assert_eq!(app.curr_tab, 1);
+    }
+
+    #[tokio::test]
+    async fn loading_stats_without_session_is_reported() {
+        let mut app = app(&["a"]);
+        dispatch_all(&mut app, &[Action::ToggleStats, Action::MoveDown]).await;
+        assert!(app.curr_tab().toggle_stats_auto_load);
+        assert_eq!(app.curr_tab().message, "Failed to get zookeeper client");
+    }
+
+    #[tokio::test]
+    async fn diff_scroll_stops_at_last_line() {
+        let mut app = app(&["a"]);
+        let entry = |path: &str| DiffEntry {
+            path: path.to_owned(),
+            kind: DiffKind::Added,
+            left: None,
+            right: None,
+        };
+        let tab = app.curr_tab_mut();
+        tab.state = TabState::DiffView;
+        tab.diff = Some(SubtreeDiff {
+            entries: vec![entry("/a"), entry("/b")],
+            ..Default::default()
+        });
+        dispatch_all(&mut app, &[Action::MoveDown; 5]).await;
+        assert_eq!(app.curr_tab().diff_scroll, 1);
+        dispatch_all(&mut app, &[Action::MoveUp]).await;
+        assert_eq!(app.curr_tab().diff_scroll, 0);
+    }
+
+    #[tokio::test]
+    async fn quit_breaks() {
+        let mut app = app(&[]);
+        assert_eq!(app.dispatch(Action::Quit).await, ControlFlow::Break(()));
+    }
+}
@@ -0,0 +1,107 @@
+use super::{action::Action, keymap::KeyContext, state::TabState, App};
+use crate::{config::Bookmark, zk_path::ZkPath};
+use std::mem;
+
+impl App {
+    pub(crate) fn open_bookmark_name(&mut self) {
+        let path = self.tab_path();
+        let tab = self.curr_tab_mut();
+        tab.input_buf = path.basename().to_owned();
+        tab.state = TabState::EditBookmarkName;
+    }
+
+    /// Bookmarks the current node under the name typed in `input_buf`.
+    pub(crate) fn save_bookmark(&mut self) {
+        let name = mem::take(&mut self.curr_tab_mut().input_buf)
+            .trim()
+            .to_owned();
+        self.curr_tab_mut().state = TabState::Tab;
+        if name.is_empty() {
+            self.set_tab_message("Bookmark name must not be empty".to_owned());
+            return;
+        }
+        let path = self.tab_path();
+        self.config.upsert_bookmark(Bookmark {
+            name: name.clone(),
+            path: path.to_string(),
+        });
+        self.save_bookmarks(format!("Bookmarked {path} as {name}"));
+    }
+
+    pub(crate) fn open_bookmarks(&mut self) {
+        if self.config.bookmarks.is_empty() {
+            let hint = self.keymap.hints(
+                KeyContext::Nodes,
+                &[(Action::AddBookmark, "bookmark the current node")],
+            );
+            self.set_tab_message(format!("No bookmarks yet, press {hint}"));
+            return;
+        }
+        if self.bookmark_list_state.selected().is_none() {
+            self.bookmark_list_state.select(Some(0));
+        }
+        self.curr_tab_mut().state = TabState::Bookmarks;
+    }
+
+    pub(crate) fn next_bookmark(&mut self) {
+        let len = self.config.bookmarks.len();
+        let i = match self.bookmark_list_state.selected() {
+            Some(i) if i + 1 < len => i + 1,
+            _ => 0,
+        };
+        self.bookmark_list_state.select(Some(i));
+    }
+
+    pub(crate) fn previous_bookmark(&mut self) {
+        let len = self.config.bookmarks.len();
+        let i = match self.bookmark_list_state.selected() {
+            Some(0) | None => len.saturating_sub(1),
+            Some(i) => i - 1,
+        };
+        self.bookmark_list_state.select(Some(i));
+    }
+
+    pub(crate) fn delete_bookmark(&mut self) {
+        let Some(i) = self.bookmark_list_state.selected() else {
+            return;
+        };
+        if i >= self.config.bookmarks.len() {
+            return;
+        }
+        let bookmark = self.config.bookmarks.remove(i);
+        let len = self.config.bookmarks.len();
+        self.bookmark_list_state
+            .select(Some(i.min(len.saturating_sub(1))).filter(|_| len > 0));
+        if len == 0 {
+            self.curr_tab_mut().state = TabState::Tab;
+        }
+        self.save_bookmarks(format!("Bookmark {} deleted", bookmark.name));
+    }
+
+    /// Opens the bookmark at `index`, the selected one in the picker when `None`.
+    pub(crate) async fn open_bookmark(&mut self, index: Option<usize>) {
+        let Some(bookmark) = index
+            .or(self.bookmark_list_state.selected())
+            .and_then(|i| self.config.bookmarks.get(i))
+        else {
+            return;
+        };
+        let path = match ZkPath::parse(&bookmark.path) {
+            Ok(path) => path,
+            Err(e) => {
+                self.set_tab_message(e.to_string());
+                return;
+            }
+        };
+        if self.open_existing_path(&path).await {
+            self.curr_tab_mut().state = TabState::Tab;
+        }
+    }
+
+    fn save_bookmarks(&mut self, message: String) {
+        match self.save_config() {
+            Ok(_) => self.set_tab_message(message),
+            Err(e) => self.set_tab_message(e),
+        }
+    }
+}
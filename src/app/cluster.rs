@@ -0,0 +1,263 @@
+use super::{
+    connection::Connection,
+    environment::Environment,
+    mode::AppMode,
+    state::TabState,
+    zk_ops::{DEFAULT_CONNECT_TIMEOUT, DEFAULT_SESSION_TIMEOUT},
+    App,
+};
+use crate::{config::Profile, errors::AppResult, tab::Tab, zk::WatchChannel, zk_path::ZkPath};
+use ratatui::{
+    style::{palette::tailwind, Color, Stylize},
+    text::Span,
+};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+use zookeeper_async::{KeeperState, ZooKeeper};
+
+/// Zookeeper session shared by the tabs browsing the same cluster.
+#[derive(Default)]
+pub struct Cluster {
+    pub name: String, // profile name, or the first host when connected without a profile
+    pub environment: Option<Environment>,
+    pub connection: String,
+    pub auth: String,
+    pub session_timeout: Duration,
+    pub connect_timeout: Duration,
+    pub mode: AppMode,
+    pub start_path: Option<String>,
+    pub zk: Option<ZooKeeper>, //TODO: Get rid from Option
+    pub session_state: Option<KeeperState>,
+    pub next_reconnect: Option<Instant>,
+    pub watches: WatchChannel,
+    pub own_changes: OwnChanges,
+}
+
+/// Paths changed through a session, remembered until their watch event arrives so the event is
+/// not reported as a change of another client. An entry whose event never comes expires.
+#[derive(Default)]
+pub struct OwnChanges(HashMap<String, Instant>);
+
+impl OwnChanges {
+    /// Records `path` and its parent, whose children changed, for `expiry`.
+    pub fn record(&mut self, path: &ZkPath, expiry: Duration) {
+        let deadline = Instant::now() + expiry;
+        self.0.insert(path.to_string(), deadline);
+        if let Some(parent) = path.parent() {
+            self.0.insert(parent.to_string(), deadline);
+        }
+    }
+
+    /// Whether the event on `path` comes from a recorded change, forgetting the change.
+    pub fn take(&mut self, path: &str) -> bool {
+        let now = Instant::now();
+        self.0.retain(|_, deadline| *deadline > now);
+        self.0.remove(path).is_some()
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+impl Cluster {
+    pub fn chroot(&self) -> Option<String> {
+        self.connection
+            .parse::<Connection>()
+            .ok()
+            .and_then(|connection| connection.chroot)
+    }
+
+    pub fn from_profile(profile: &Profile, mode: AppMode) -> Self {
+        Self {
+            name: profile.name.clone(),
+            environment: profile.environment,
+            connection: profile.connect.clone(),
+            auth: profile.auth.clone().unwrap_or_default(),
+            session_timeout: profile
+                .session_timeout_ms
+                .map(Duration::from_millis)
+                .unwrap_or(DEFAULT_SESSION_TIMEOUT),
+            connect_timeout: profile
+                .connect_timeout_ms
+                .map(Duration::from_millis)
+                .unwrap_or(DEFAULT_CONNECT_TIMEOUT),
+            mode,
+            start_path: profile.start_path.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// Opens a new session, closing the previous one and dropping its watch events.
+    /// A scheduled reconnect is only dropped once the session is open.
+    pub async fn connect(&mut self) -> AppResult<()> {
+        self.close().await;
+        let zk = App::connect_default(
+            &self.connection,
+            &self.auth,
+            self.session_timeout,
+            self.connect_timeout,
+            self.watches.watcher(),
+        )
+        .await?;
+        self.zk = Some(zk);
+        self.next_reconnect = None;
+        self.session_state = Some(KeeperState::SyncConnected);
+        self.own_changes.clear();
+        Ok(())
+    }
+
+    pub async fn disconnect(&mut self) {
+        self.close().await;
+        self.next_reconnect = None;
+        self.session_state = None;
+    }
+
+    async fn close(&mut self) {
+        if let Some(zk) = self.zk.take() {
+            let _ = zk.close().await;
+        }
+        self.watches.renew();
+    }
+
+    pub fn reconnect_due(&self) -> bool {
+        self.next_reconnect
+            .is_some_and(|next_reconnect| Instant::now() >= next_reconnect)
+    }
+
+    /// Tab colour of the cluster, blue when the profile does not name an environment.
+    pub fn color(&self) -> Color {
+        self.environment
+            .map_or(tailwind::BLUE.c900, |environment| environment.color())
+    }
+
+    pub fn title(&self) -> Span<'static> {
+        format!("  {}  ", self.name)
+            .fg(tailwind::SLATE.c200)
+            .bg(self.color())
+    }
+}
+
+impl App {
+    pub(crate) fn open_clusters(&mut self) {
+        if self.config.profiles.is_empty() {
+            self.set_tab_message("No profiles saved, save one on the connection screen".to_owned());
+            return;
+        }
+        if self.cluster_list_state.selected().is_none() {
+            self.cluster_list_state.select(Some(0));
+        }
+        self.curr_tab_mut().state = TabState::Clusters;
+    }
+
+    pub(crate) fn next_cluster(&mut self) {
+        let len = self.config.profiles.len();
+        if len == 0 {
+            return;
+        }
+        let i = match self.cluster_list_state.selected() {
+            Some(i) if i + 1 < len => i + 1,
+            _ => 0,
+        };
+        self.cluster_list_state.select(Some(i));
+    }
+
+    pub(crate) fn previous_cluster(&mut self) {
+        let len = self.config.profiles.len();
+        if len == 0 {
+            return;
+        }
+        let i = match self.cluster_list_state.selected() {
+            Some(0) | None => len - 1,
+            Some(i) => i - 1,
+        };
+        self.cluster_list_state.select(Some(i));
+    }
+
+    /// Opens a tab on the selected profile, reusing its session when another tab is already on it.
+    pub(crate) async fn open_cluster_tab(&mut self) {
+        let Some(profile) = self
+            .cluster_list_state
+            .selected()
+            .and_then(|i| self.config.profiles.get(i))
+            .cloned()
+        else {
+            return;
+        };
+        self.curr_tab_mut().state = TabState::Tab;
+
+        let existing = self
+            .clusters
+            .iter()
+            .position(|cluster| cluster.name == profile.name);
+        let cluster = match existing {
+            Some(index) if self.clusters[index].zk.is_some() => index,
+            _ => {
+                let mode = if self.mode_locked {
+                    self.mode
+                } else {
+                    profile.mode.unwrap_or_default()
+                };
+                let mut cluster = Cluster::from_profile(&profile, mode);
+                cluster.watches = self.watch_channel(existing.unwrap_or(self.clusters.len()));
+                if let Err(e) = cluster.connect().await {
+                    self.set_tab_message(format!("Failed to connect to {} : {e}", profile.name));
+                    return;
+                }
+                match existing {
+                    Some(index) => {
+                        self.clusters[index] = cluster;
+                        index
+                    }
+                    None => {
+                        self.clusters.push(cluster);
+                        self.clusters.len() - 1
+                    }
+                }
+            }
+        };
+
+        let tab = Tab {
+            cluster,
+            tree_mode: self.curr_tab().tree_mode,
+            toggle_stats_auto_load: self.curr_tab().toggle_stats_auto_load,
+            ..Default::default()
+        };
+        self.add_tab(tab);
+        self.open_start_path().await;
+    }
+
+    /// Closes the session of `cluster` once the last tab on it is gone.
+    pub(crate) async fn release_cluster(&mut self, cluster: usize) {
+        if self.tabs.iter().all(|tab| tab.cluster != cluster) {
+            self.clusters[cluster].disconnect().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn own_change_is_taken_once() {
+        let mut changes = OwnChanges::default();
+        changes.record(
+            &ZkPath::parse("/app/node").unwrap(),
+            Duration::from_secs(60),
+        );
+        assert!(changes.take("/app/node"));
+        assert!(!changes.take("/app/node"));
+        assert!(changes.take("/app"));
+    }
+
+    #[test]
+    fn expired_own_change_is_foreign() {
+        let mut changes = OwnChanges::default();
+        changes.record(&ZkPath::parse("/app/node").unwrap(), Duration::ZERO);
+        assert!(!changes.take("/app/node"));
+        assert!(!changes.take("/app"));
+    }
+}
@@ -1,13 +1,183 @@
-use std::{fmt, net::IpAddr};
+use crate::errors::AppError;
+use std::{fmt, str::FromStr};
 
-#[derive(Debug)]
+pub const DEFAULT_PORT: u16 = 2181;
+
+/// Zookeeper connect string: `host1:port1,host2:port2/optional/chroot`.
+#[derive(Debug, Clone, PartialEq)]
 pub struct Connection {
-    pub addr: IpAddr,
+    pub hosts: Vec<Host>,
+    pub chroot: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Host {
+    pub name: String,
     pub port: u16,
 }
 
+impl FromStr for Connection {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (hosts, chroot) = match s.find('/') {
+            Some(i) => (&s[..i], parse_chroot(&s[i..])?),
+            None => (s, None),
+        };
+        if hosts.is_empty() {
+            return Err(invalid("no hosts given"));
+        }
+
+        let hosts = hosts
+            .split(',')
+            .map(str::parse)
+            .collect::<Result<Vec<Host>, _>>()?;
+
+        Ok(Self { hosts, chroot })
+    }
+}
+
+impl FromStr for Host {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(invalid("empty host"));
+        }
+
+        let (name, port) = if let Some(rest) = s.strip_prefix('[') {
+            // IPv6 literal: [::1]:2181
+            let (name, rest) = rest
+                .split_once(']')
+                .ok_or_else(|| invalid(&format!("unclosed '[' in {s}")))?;
+            match rest.strip_prefix(':') {
+                Some(port) => (name, Some(port)),
+                None if rest.is_empty() => (name, None),
+                None => return Err(invalid(&format!("unexpected {rest} after {name}"))),
+            }
+        } else {
+            match s.split_once(':') {
+                Some((name, port)) => (name, Some(port)),
+                None => (s, None),
+            }
+        };
+
+        let valid_name = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | ':'));
+        if !valid_name {
+            return Err(invalid(&format!("invalid host name {name}")));
+        }
+
+        let port = match port {
+            Some(port) => port
+                .parse::<u16>()
+                .ok()
+                .filter(|port| *port != 0)
+                .ok_or_else(|| invalid(&format!("invalid port {port} for {name}")))?,
+            None => DEFAULT_PORT,
+        };
+
+        Ok(Self {
+            name: name.to_owned(),
+            port,
+        })
+    }
+}
+
+fn parse_chroot(chroot: &str) -> Result<Option<String>, AppError> {
+    if chroot == "/" {
+        return Ok(None);
+    }
+    if chroot.ends_with('/') || chroot.contains("//") {
+        return Err(invalid(&format!("invalid chroot path {chroot}")));
+    }
+    Ok(Some(chroot.to_owned()))
+}
+
+fn invalid(reason: &str) -> AppError {
+    AppError::InvalidConnectionString(reason.to_owned())
+}
+
+impl fmt::Display for Host {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.name.contains(':') {
+            write!(f, "[{}]:{}", self.name, self.port)
+        } else {
+            write!(f, "{}:{}", self.name, self.port)
+        }
+    }
+}
+
 impl fmt::Display for Connection {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}:{}", self.addr, self.port)
+        let hosts = self
+            .hosts
+            .iter()
+            .map(Host::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        write!(f, "{hosts}{}", self.chroot.as_deref().unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn host(name: &str, port: u16) -> Host {
+        Host {
+            name: name.to_owned(),
+            port,
+        }
+    }
+
+    #[test]
+    fn parses_hosts_and_chroot() {
+        let connection: Connection = " zk1.internal:2182, zk2 ,[::1]:2183/app/config "
+            .parse()
+            .unwrap();
+        assert_eq!(
+            connection.hosts,
+            vec![
+                host("zk1.internal", 2182),
+                host("zk2", DEFAULT_PORT),
+                host("::1", 2183)
+            ]
+        );
+        assert_eq!(connection.chroot.as_deref(), Some("/app/config"));
+        assert_eq!(
+            connection.to_string(),
+            "zk1.internal:2182,zk2:2181,[::1]:2183/app/config"
+        );
+    }
+
+    #[test]
+    fn root_chroot_is_dropped() {
+        let connection: Connection = "localhost/".parse().unwrap();
+        assert_eq!(connection.chroot, None);
+        assert_eq!(connection.to_string(), "localhost:2181");
+    }
+
+    #[test]
+    fn rejects_invalid_connect_strings() {
+        for connect in [
+            "",
+            "/app",
+            "zk1,,zk2",
+            "zk1:0",
+            "zk1:65536",
+            "zk1:port",
+            "zk 1",
+            "[::1",
+            "[::1]2181",
+            "zk1/app/",
+            "zk1/app//config",
+        ] {
+            assert!(connect.parse::<Connection>().is_err(), "{connect}");
+        }
     }
 }
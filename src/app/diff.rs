@@ -0,0 +1,123 @@
+use super::{connection::Connection, state::TabState, App};
+use crate::{diff::SubtreeDiff, errors::zk_error_message, snapshot::Snapshot, zk_path::ZkPath};
+
+impl App {
+    pub(crate) fn open_diff(&mut self) {
+        let path = self.tab_path().to_string();
+        let tab = self.curr_tab_mut();
+        tab.node_path_buf = path.clone();
+        tab.input_buf = path;
+        tab.diff = None;
+        tab.state = TabState::EditDiffLeft;
+    }
+
+    /// Cycles through the diff fields, the auth one only when another cluster is compared.
+    pub(crate) fn next_diff_field(&mut self) {
+        let tab = self.curr_tab();
+        self.curr_tab_mut().state = match tab.state {
+            TabState::EditDiffLeft => TabState::EditDiffRight,
+            TabState::EditDiffRight => TabState::EditDiffConnection,
+            TabState::EditDiffConnection if !tab.diff_connection_buf.trim().is_empty() => {
+                TabState::EditDiffAuth
+            }
+            _ => TabState::EditDiffLeft,
+        };
+    }
+
+    pub(crate) fn diff_field_mut(&mut self) -> &mut String {
+        let tab = self.curr_tab_mut();
+        match tab.state {
+            TabState::EditDiffRight => &mut tab.input_buf,
+            TabState::EditDiffConnection => &mut tab.diff_connection_buf,
+            TabState::EditDiffAuth => &mut tab.diff_auth_buf,
+            _ => &mut tab.node_path_buf,
+        }
+    }
+
+    /// Compares the left path of the current connection with the right path, which is read
+    /// through `diff_connection_buf` with the `diff_auth_buf` credentials when it is set.
+    pub(crate) async fn compare_subtrees(&mut self) {
+        let paths = ZkPath::parse(self.curr_tab().node_path_buf.trim()).and_then(|left| {
+            ZkPath::parse(self.curr_tab().input_buf.trim()).map(|right| (left, right))
+        });
+        let (left_path, right_path) = match paths {
+            Ok(paths) => paths,
+            Err(e) => {
+                self.set_tab_message(e.to_string());
+                return;
+            }
+        };
+        let Some(ref zk) = self.cluster().zk else {
+            "Failed to get zookeeper client".clone_into(&mut self.curr_tab_mut().message);
+            return;
+        };
+        let right_connection = self.curr_tab().diff_connection_buf.trim().to_owned();
+
+        let left = match Snapshot::take(zk, &left_path).await {
+            Ok(left) => left,
+            Err((path, e)) => {
+                self.set_tab_message(zk_error_message("read", &path, &e));
+                return;
+            }
+        };
+        let right = if right_connection.is_empty() {
+            Snapshot::take(zk, &right_path).await
+        } else {
+            let connection = match right_connection.parse::<Connection>() {
+                Ok(connection) => connection,
+                Err(e) => {
+                    self.set_tab_message(e.to_string());
+                    return;
+                }
+            };
+            let right_zk = match App::connect_default(
+                &connection.to_string(),
+                self.curr_tab().diff_auth_buf.trim(),
+                self.cluster().session_timeout,
+                self.cluster().connect_timeout,
+                |_| {},
+            )
+            .await
+            {
+                Ok(right_zk) => right_zk,
+                Err(e) => {
+                    self.set_tab_message(format!("Failed to connect to {connection} : {e}"));
+                    return;
+                }
+            };
+            let right = Snapshot::take(&right_zk, &right_path).await;
+            let _ = right_zk.close().await;
+            right
+        };
+        let right = match right {
+            Ok(right) => right,
+            Err((path, e)) => {
+                self.set_tab_message(zk_error_message("read", &path, &e));
+                return;
+            }
+        };
+
+        let diff = SubtreeDiff::compare(&left, &right);
+        let tab = self.curr_tab_mut();
+        tab.message = diff.summary();
+        tab.diff = Some(diff);
+        tab.diff_scroll = 0;
+        tab.state = TabState::DiffView;
+    }
+
+    /// Scrolls one line down, stopping with the last diff line at the top.
+    pub(crate) fn scroll_diff_down(&mut self) {
+        let tab = self.curr_tab_mut();
+        let last = tab
+            .diff
+            .as_ref()
+            .map_or(0, |diff| diff.lines().len().saturating_sub(1));
+        let last = u16::try_from(last).unwrap_or(u16::MAX);
+        tab.diff_scroll = tab.diff_scroll.saturating_add(1).min(last);
+    }
+
+    pub(crate) fn scroll_diff_up(&mut self) {
+        let tab = self.curr_tab_mut();
+        tab.diff_scroll = tab.diff_scroll.saturating_sub(1);
+    }
+}
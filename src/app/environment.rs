@@ -0,0 +1,33 @@
+use core::fmt;
+use ratatui::style::{palette::tailwind, Color};
+use serde::{Deserialize, Serialize};
+
+/// Deployment environment of a profile, used to colour the tabs connected to it.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Environment {
+    Development,
+    Staging,
+    Production,
+}
+
+impl Environment {
+    pub fn color(&self) -> Color {
+        match self {
+            Environment::Development => tailwind::GREEN.c800,
+            Environment::Staging => tailwind::AMBER.c800,
+            Environment::Production => tailwind::RED.c800,
+        }
+    }
+}
+
+impl fmt::Display for Environment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Environment::Development => "development",
+            Environment::Staging => "staging",
+            Environment::Production => "production",
+        };
+        write!(f, "{name}")
+    }
+}
@@ -0,0 +1,58 @@
+use super::{
+    state::{AppState, TabState},
+    App,
+};
+use zookeeper_async::{WatchedEvent, WatchedEventType};
+
+impl App {
+    pub fn is_deleting(&self) -> bool {
+        self.state == AppState::Tab && self.curr_tab().state == TabState::DeletingNodes
+    }
+
+    /// Periodic work: the next recursive delete batch and the due reconnects.
+    pub(crate) async fn handle_tick(&mut self) {
+        if self.is_deleting() {
+            match self.curr_tab().delete_plan {
+                Some(ref plan) if !plan.is_finished() => self.delete_next_batch().await,
+                _ => self.finish_recursive_delete().await,
+            }
+        }
+        for cluster in 0..self.clusters.len() {
+            if self.clusters[cluster].reconnect_due() {
+                self.reconnect(cluster).await;
+            }
+        }
+    }
+
+    /// Dispatches a watch or session event, dropping the ones of a closed session.
+    pub(crate) async fn handle_watch(
+        &mut self,
+        cluster: usize,
+        generation: u64,
+        event: WatchedEvent,
+    ) {
+        if self.state != AppState::Tab || !self.clusters[cluster].watches.is_current(generation) {
+            return;
+        }
+        match event.event_type {
+            WatchedEventType::None => self.handle_session_event(cluster, event.keeper_state).await,
+            _ => self.handle_watched_event(cluster, event).await,
+        }
+    }
+
+    /// Reports a cancelled operation, a cancelled recursive delete stops after the current batch.
+    pub(crate) fn operation_cancelled(&mut self) {
+        if self.state != AppState::Tab {
+            self.connection_error = Some("Connection cancelled".to_owned());
+            return;
+        }
+        let tab = self.curr_tab_mut();
+        if let Some(ref mut plan) = tab.delete_plan {
+            if tab.state == TabState::DeletingNodes {
+                plan.stopped = true;
+                return;
+            }
+        }
+        self.append_tab_message("Operation cancelled\n".to_owned());
+    }
+}
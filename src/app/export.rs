@@ -0,0 +1,47 @@
+use super::{state::TabState, App};
+use crate::{errors::zk_error_message, snapshot::Snapshot};
+use std::{mem, path::Path};
+
+impl App {
+    pub(crate) fn open_export(&mut self) {
+        let root = self.tab_path();
+        let name = match root.basename() {
+            "" => "root",
+            name => name,
+        };
+        let tab = self.curr_tab_mut();
+        tab.input_buf = format!("{name}.json");
+        tab.node_path_buf = root.to_string();
+        tab.state = TabState::ExportSubtree;
+    }
+
+    /// Writes the subtree under `node_path_buf` to the file named in `input_buf`.
+    pub(crate) async fn export_subtree(&mut self) {
+        let file = mem::take(&mut self.curr_tab_mut().input_buf)
+            .trim()
+            .to_owned();
+        self.curr_tab_mut().state = TabState::Tab;
+        if file.is_empty() {
+            self.set_tab_message("Export file path must not be empty".to_owned());
+            return;
+        }
+        let Some(root) = self.parse_node_path_buf() else {
+            return;
+        };
+        let Some(ref zk) = self.cluster().zk else {
+            "Failed to get zookeeper client".clone_into(&mut self.curr_tab_mut().message);
+            return;
+        };
+        let message = match Snapshot::take(zk, &root).await {
+            Ok(snapshot) => match snapshot.save(Path::new(&file)) {
+                Ok(_) => format!(
+                    "Exported {} nodes under {root} to {file}",
+                    snapshot.nodes.len()
+                ),
+                Err(e) => format!("Failed to write {file} : {e}"),
+            },
+            Err((path, e)) => zk_error_message("export", &path, &e),
+        };
+        self.set_tab_message(message);
+    }
+}
@@ -0,0 +1,114 @@
+use super::App;
+use crate::zk_path::ZkPath;
+
+pub const HISTORY_SIZE: usize = 100;
+
+/// Browser-style back/forward stacks of visited node paths.
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    back: Vec<ZkPath>,
+    forward: Vec<ZkPath>,
+}
+
+impl History {
+    /// Remembers `from` before moving somewhere else, dropping the forward history.
+    pub fn visit(&mut self, from: ZkPath) {
+        if self.back.last() != Some(&from) {
+            self.back.push(from);
+        }
+        if self.back.len() > HISTORY_SIZE {
+            self.back.remove(0);
+        }
+        self.forward.clear();
+    }
+
+    fn back(&mut self, current: ZkPath) -> Option<ZkPath> {
+        let path = self.back.pop()?;
+        self.forward.push(current);
+        Some(path)
+    }
+
+    fn forward(&mut self, current: ZkPath) -> Option<ZkPath> {
+        let path = self.forward.pop()?;
+        self.back.push(current);
+        Some(path)
+    }
+}
+
+impl App {
+    pub(crate) fn record_visit(&mut self) {
+        let path = self.tab_path();
+        self.curr_tab_mut().history.visit(path);
+    }
+
+    pub(crate) async fn history_back(&mut self) {
+        let current = self.tab_path();
+        match self.curr_tab_mut().history.back(current) {
+            Some(path) => self.show_path(&path).await,
+            None => self.set_tab_message("No previous node in the history".to_owned()),
+        }
+    }
+
+    pub(crate) async fn history_forward(&mut self) {
+        let current = self.tab_path();
+        match self.curr_tab_mut().history.forward(current) {
+            Some(path) => self.show_path(&path).await,
+            None => self.set_tab_message("No next node in the history".to_owned()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(path: &str) -> ZkPath {
+        ZkPath::parse(path).unwrap()
+    }
+
+    #[test]
+    fn goes_back_and_forward() {
+        let mut history = History::default();
+        history.visit(path("/a"));
+        history.visit(path("/b"));
+        assert_eq!(history.back(path("/c")), Some(path("/b")));
+        assert_eq!(history.back(path("/b")), Some(path("/a")));
+        assert_eq!(history.back(path("/a")), None);
+        assert_eq!(history.forward(path("/a")), Some(path("/b")));
+        assert_eq!(history.forward(path("/b")), Some(path("/c")));
+        assert_eq!(history.forward(path("/c")), None);
+    }
+
+    #[test]
+    fn skips_consecutive_duplicates() {
+        let mut history = History::default();
+        history.visit(path("/a"));
+        history.visit(path("/a"));
+        history.visit(path("/b"));
+        history.visit(path("/a"));
+        assert_eq!(history.back, [path("/a"), path("/b"), path("/a")]);
+    }
+
+    #[test]
+    fn new_visit_clears_forward() {
+        let mut history = History::default();
+        history.visit(path("/a"));
+        assert_eq!(history.back(path("/b")), Some(path("/a")));
+        history.visit(path("/a"));
+        assert_eq!(history.forward(path("/c")), None);
+    }
+
+    #[test]
+    fn keeps_the_latest_visits() {
+        let mut history = History::default();
+        for i in 0..HISTORY_SIZE + 5 {
+            history.visit(path(&format!("/n{i}")));
+        }
+        assert_eq!(history.back.len(), HISTORY_SIZE);
+        assert_eq!(history.back.first(), Some(&path("/n5")));
+        assert_eq!(
+            history.back(ZkPath::root()),
+            Some(path(&format!("/n{}", HISTORY_SIZE + 4)))
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use super::{state::TabState, App};
+use crate::{
+    import::{ImportAction, ImportPlan},
+    snapshot::Snapshot,
+};
+use std::path::Path;
+
+impl App {
+    pub(crate) fn open_import(&mut self) {
+        let target = self.tab_path().to_string();
+        let tab = self.curr_tab_mut();
+        tab.node_path_buf = target;
+        tab.import_plan = None;
+        tab.state = TabState::ImportSnapshot;
+    }
+
+    pub(crate) fn next_import_strategy(&mut self) {
+        let strategy = self.curr_tab().import_strategy.next();
+        self.curr_tab_mut().import_strategy = strategy;
+    }
+
+    /// Loads the snapshot named in `input_buf` and plans the import without writing anything.
+    pub(crate) async fn plan_import(&mut self) {
+        let file = self.curr_tab().input_buf.trim().to_owned();
+        let snapshot = match Snapshot::load(Path::new(&file)) {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                self.set_tab_message(format!("Failed to read {file} : {e}"));
+                return;
+            }
+        };
+        let Some(target) = self.parse_node_path_buf() else {
+            return;
+        };
+        let Some(ref zk) = self.cluster().zk else {
+            "Failed to get zookeeper client".clone_into(&mut self.curr_tab_mut().message);
+            return;
+        };
+
+        let strategy = self.curr_tab().import_strategy;
+        let chroot = self.cluster().chroot();
+        let plan = ImportPlan::build(zk, chroot.as_deref(), &snapshot, &target, strategy).await;
+        let tab = self.curr_tab_mut();
+        tab.message = plan.summary();
+        tab.import_plan = Some(plan);
+        tab.state = TabState::ImportPreview;
+    }
+
+    pub(crate) async fn apply_import(&mut self) {
+        if !self.ensure_can_write() {
+            return;
+        }
+        let Some(mut plan) = self.curr_tab_mut().import_plan.take() else {
+            return;
+        };
+        let Some(ref zk) = self.cluster().zk else {
+            "Failed to get zookeeper client".clone_into(&mut self.curr_tab_mut().message);
+            return;
+        };
+
+        plan.apply(zk).await;
+        for entry in plan.entries.iter().filter(|entry| {
+            matches!(entry.action, ImportAction::Create | ImportAction::Update)
+                && entry.error.is_none()
+        }) {
+            self.record_own_change(&entry.path);
+        }
+
+        self.curr_tab_mut().input_buf.clear();
+        self.curr_tab_mut().state = TabState::Tab;
+        self.reload_listing().await;
+        self.set_tab_message(plan.summary());
+    }
+}
@@ -0,0 +1,558 @@
+use super::{
+    action::Action,
+    state::{AppState, TabState},
+    App,
+};
+use crate::errors::{AppError, AppResult};
+use core::fmt;
+use crossterm::event::KeyCode;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Keys remapped in the config file: context, then action name, then the keys replacing its defaults.
+pub type KeyOverrides = BTreeMap<KeyContext, BTreeMap<String, Vec<String>>>;
+
+/// Screens sharing the same key bindings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum KeyContext {
+    Connection, // profile list of the connection screen
+    ConnectionFailed,
+    Input, // text inputs, characters without a binding are typed in
+    Nodes,
+    NodeData,
+    Selector, // create mode and ACL preset choices of the create node form
+    Preview,  // import and copy previews
+    List,     // clusters, bookmarks, search results and the diff
+    AclEditor,
+    Deleting,
+}
+
+impl fmt::Display for KeyContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            KeyContext::Connection => "connection",
+            KeyContext::ConnectionFailed => "connection-failed",
+            KeyContext::Input => "input",
+            KeyContext::Nodes => "nodes",
+            KeyContext::NodeData => "node-data",
+            KeyContext::Selector => "selector",
+            KeyContext::Preview => "preview",
+            KeyContext::List => "list",
+            KeyContext::AclEditor => "acl-editor",
+            KeyContext::Deleting => "deleting",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Actions bound to the keys of each context, in the order they are listed.
+pub struct KeyMap {
+    bindings: HashMap<KeyContext, Vec<(KeyCode, Action)>>,
+}
+
+impl KeyMap {
+    pub fn action(&self, context: KeyContext, key: KeyCode) -> Option<Action> {
+        self.bindings
+            .get(&context)?
+            .iter()
+            .find(|(code, _)| *code == key)
+            .map(|(_, action)| *action)
+    }
+
+    /// Default bindings with the actions remapped in the config file. A key left bound to two
+    /// actions of the same context is a conflict, and so is a character bound in text inputs as
+    /// it could no longer be typed. Every problem found is reported at once.
+    pub fn from_config(overrides: &KeyOverrides) -> AppResult<Self> {
+        let mut keymap = KeyMap::default();
+        let mut problems = Vec::new();
+        for (context, actions) in overrides {
+            let bindings = keymap.bindings.entry(*context).or_default();
+            let defaults = bindings.clone();
+            for (name, keys) in actions {
+                let Some(action) = defaults
+                    .iter()
+                    .map(|(_, action)| *action)
+                    .find(|action| action.to_string() == *name)
+                else {
+                    problems.push(format!("unknown action {name} in {context}"));
+                    continue;
+                };
+                bindings.retain(|(_, bound)| *bound != action);
+                for key in keys {
+                    match parse_key(key) {
+                        Some(code) => bindings.push((code, action)),
+                        None => problems.push(format!("unknown key {key} for {name} in {context}")),
+                    }
+                }
+            }
+            for (i, (code, action)) in bindings.iter().enumerate() {
+                if let (KeyContext::Input, KeyCode::Char(_)) = (context, code) {
+                    problems.push(format!(
+                        "{} is bound to {action} in {context} and could not be typed",
+                        key_name(*code)
+                    ));
+                }
+                if let Some((_, other)) = bindings[..i]
+                    .iter()
+                    .find(|(bound, other)| bound == code && other != action)
+                {
+                    problems.push(format!(
+                        "{} is bound to both {other} and {action} in {context}",
+                        key_name(*code)
+                    ));
+                }
+            }
+        }
+        if problems.is_empty() {
+            Ok(keymap)
+        } else {
+            Err(AppError::InvalidKeyBindings(problems.join(", ")))
+        }
+    }
+
+    /// Keys bound to `action` as shown in the hints, the tab or bookmark numbers as a range.
+    pub fn keys(&self, context: KeyContext, action: Action) -> Option<String> {
+        let keys = |action: Action| -> Vec<String> {
+            self.bindings
+                .get(&context)
+                .into_iter()
+                .flatten()
+                .filter(|(_, bound)| *bound == action)
+                .map(|(code, _)| key_name(*code))
+                .collect()
+        };
+        if let Action::Select(_) = action {
+            let first = keys(Action::Select(0)).into_iter().next()?;
+            return Some(match keys(Action::Select(8)).into_iter().next() {
+                Some(last) => format!("{first}-{last}"),
+                None => first,
+            });
+        }
+        Some(keys(action).join("/")).filter(|keys| !keys.is_empty())
+    }
+
+    /// Bottom bar hint such as "Esc to cancel | Enter to save", unbound actions are left out.
+    pub fn hints(&self, context: KeyContext, hints: &[(Action, &str)]) -> String {
+        hints
+            .iter()
+            .filter_map(|(action, label)| {
+                self.keys(context, *action)
+                    .map(|keys| format!("{keys} to {label}"))
+            })
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+}
+
+/// Key named in the config file: a single character or the name of a special key.
+pub fn parse_key(name: &str) -> Option<KeyCode> {
+    let mut chars = name.chars();
+    if let (Some(value), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(value));
+    }
+    let code = match name.to_ascii_lowercase().as_str() {
+        "esc" => KeyCode::Esc,
+        "enter" => KeyCode::Enter,
+        "tab" => KeyCode::Tab,
+        "backspace" => KeyCode::Backspace,
+        "delete" => KeyCode::Delete,
+        "insert" => KeyCode::Insert,
+        "space" => KeyCode::Char(' '),
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        function => KeyCode::F(function.strip_prefix('f')?.parse().ok()?),
+    };
+    Some(code)
+}
+
+pub fn key_name(code: KeyCode) -> String {
+    match code {
+        KeyCode::Char(' ') => "Space".to_owned(),
+        KeyCode::Char(value) => value.to_string(),
+        KeyCode::Up => "↑".to_owned(),
+        KeyCode::Down => "↓".to_owned(),
+        KeyCode::Left => "←".to_owned(),
+        KeyCode::Right => "→".to_owned(),
+        KeyCode::PageUp => "PageUp".to_owned(),
+        KeyCode::PageDown => "PageDown".to_owned(),
+        KeyCode::F(number) => format!("F{number}"),
+        code => format!("{code:?}"),
+    }
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        let digits = (b'1'..=b'9')
+            .enumerate()
+            .map(|(index, digit)| (KeyCode::Char(digit as char), Action::Select(index)));
+        let bindings = HashMap::from([
+            (
+                KeyContext::Connection,
+                vec![
+                    (KeyCode::Char('q'), Action::Quit),
+                    (KeyCode::Esc, Action::Quit),
+                    (KeyCode::Enter, Action::Confirm),
+                    (KeyCode::Char('j'), Action::MoveDown),
+                    (KeyCode::Down, Action::MoveDown),
+                    (KeyCode::Char('k'), Action::MoveUp),
+                    (KeyCode::Up, Action::MoveUp),
+                    (KeyCode::Char('e'), Action::EditConnection),
+                    (KeyCode::Char('a'), Action::EditAuth),
+                    (KeyCode::Char('s'), Action::SaveProfile),
+                    (KeyCode::Char('x'), Action::Remove),
+                ],
+            ),
+            (
+                KeyContext::ConnectionFailed,
+                vec![
+                    (KeyCode::Char('q'), Action::Quit),
+                    (KeyCode::Esc, Action::Back),
+                    (KeyCode::Enter, Action::Confirm),
+                    (KeyCode::Char('r'), Action::Confirm),
+                    (KeyCode::Char('e'), Action::EditConnection),
+                    (KeyCode::Char('a'), Action::EditAuth),
+                ],
+            ),
+            (
+                KeyContext::Input,
+                vec![
+                    (KeyCode::Esc, Action::Back),
+                    (KeyCode::Enter, Action::Confirm),
+                    (KeyCode::Tab, Action::NextField),
+                    (KeyCode::Backspace, Action::DeleteChar),
+                    (KeyCode::Down, Action::MoveDown),
+                    (KeyCode::Up, Action::MoveUp),
+                ],
+            ),
+            (
+                KeyContext::Nodes,
+                [
+                    (KeyCode::Char('q'), Action::Quit),
+                    (KeyCode::Char('j'), Action::MoveDown),
+                    (KeyCode::Down, Action::MoveDown),
+                    (KeyCode::Char('k'), Action::MoveUp),
+                    (KeyCode::Up, Action::MoveUp),
+                    (KeyCode::Enter, Action::Confirm),
+                    (KeyCode::Esc, Action::Back),
+                    (KeyCode::Char('C'), Action::CreateNode),
+                    (KeyCode::Char('D'), Action::DeleteNode),
+                    (KeyCode::Char('R'), Action::ReadData),
+                    (KeyCode::Char('S'), Action::ToggleStats),
+                    (KeyCode::Char('A'), Action::EditAcl),
+                    (KeyCode::Char('T'), Action::ToggleTree),
+                    (KeyCode::Char('/'), Action::Filter),
+                    (KeyCode::Char('s'), Action::SearchNames),
+                    (KeyCode::Char('G'), Action::SearchData),
+                    (KeyCode::Char('g'), Action::GoToPath),
+                    (KeyCode::Char('X'), Action::Export),
+                    (KeyCode::Char('I'), Action::Import),
+                    (KeyCode::Char('F'), Action::Diff),
+                    (KeyCode::Char('['), Action::HistoryBack),
+                    (KeyCode::Char(']'), Action::HistoryForward),
+                    (KeyCode::Char('B'), Action::AddBookmark),
+                    (KeyCode::Char('b'), Action::Bookmarks),
+                    (KeyCode::Char('t'), Action::NewTab),
+                    (KeyCode::Char('Y'), Action::DuplicateTab),
+                    (KeyCode::Char('W'), Action::CloseTab),
+                    (KeyCode::Char('N'), Action::RenameTab),
+                    (KeyCode::Right, Action::NextTab),
+                    (KeyCode::Left, Action::PreviousTab),
+                    (KeyCode::Char('O'), Action::OpenClusters),
+                    (KeyCode::Char('V'), Action::ToggleSplit),
+                    (KeyCode::Tab, Action::FocusOtherPane),
+                    (KeyCode::Char('c'), Action::CopyToOtherPane),
+                ]
+                .into_iter()
+                .chain(digits.clone())
+                .collect(),
+            ),
+            (
+                KeyContext::NodeData,
+                vec![
+                    (KeyCode::Esc, Action::Back),
+                    (KeyCode::Char('J'), Action::ShowJson),
+                    (KeyCode::Char('S'), Action::ShowString),
+                    (KeyCode::Char('R'), Action::ShowRaw),
+                    (KeyCode::Char('E'), Action::EditData),
+                ],
+            ),
+            (
+                KeyContext::Selector,
+                vec![
+                    (KeyCode::Esc, Action::Back),
+                    (KeyCode::Enter, Action::Confirm),
+                    (KeyCode::Tab, Action::NextField),
+                    (KeyCode::Char('j'), Action::MoveDown),
+                    (KeyCode::Down, Action::MoveDown),
+                    (KeyCode::Right, Action::MoveDown),
+                    (KeyCode::Char('k'), Action::MoveUp),
+                    (KeyCode::Up, Action::MoveUp),
+                    (KeyCode::Left, Action::MoveUp),
+                ],
+            ),
+            (
+                KeyContext::Preview,
+                vec![
+                    (KeyCode::Esc, Action::Back),
+                    (KeyCode::Enter, Action::Confirm),
+                ],
+            ),
+            (
+                KeyContext::List,
+                [
+                    (KeyCode::Esc, Action::Back),
+                    (KeyCode::Char('q'), Action::Close),
+                    (KeyCode::Enter, Action::Confirm),
+                    (KeyCode::Char('j'), Action::MoveDown),
+                    (KeyCode::Down, Action::MoveDown),
+                    (KeyCode::Char('k'), Action::MoveUp),
+                    (KeyCode::Up, Action::MoveUp),
+                    (KeyCode::Char('x'), Action::Remove),
+                ]
+                .into_iter()
+                .chain(digits)
+                .collect(),
+            ),
+            (
+                KeyContext::AclEditor,
+                vec![
+                    (KeyCode::Esc, Action::Back),
+                    (KeyCode::Enter, Action::Confirm),
+                    (KeyCode::Char('j'), Action::MoveDown),
+                    (KeyCode::Down, Action::MoveDown),
+                    (KeyCode::Char('k'), Action::MoveUp),
+                    (KeyCode::Up, Action::MoveUp),
+                    (KeyCode::Char('n'), Action::NewEntry),
+                    (KeyCode::Char('x'), Action::Remove),
+                    (KeyCode::Char('c'), Action::TogglePermission('c')),
+                    (KeyCode::Char('d'), Action::TogglePermission('d')),
+                    (KeyCode::Char('r'), Action::TogglePermission('r')),
+                    (KeyCode::Char('w'), Action::TogglePermission('w')),
+                    (KeyCode::Char('a'), Action::TogglePermission('a')),
+                ],
+            ),
+            (KeyContext::Deleting, vec![(KeyCode::Esc, Action::Back)]),
+        ]);
+        Self { bindings }
+    }
+}
+
+impl App {
+    pub fn key_context(&self) -> KeyContext {
+        match self.state {
+            AppState::EstablishingConnection => KeyContext::Connection,
+            AppState::ConnectionFailed => KeyContext::ConnectionFailed,
+            AppState::ConfirmDeleteProfile => KeyContext::Preview,
+            AppState::EditingConnection | AppState::EditingAuth | AppState::EditingProfileName => {
+                KeyContext::Input
+            }
+            AppState::Tab => match self.curr_tab().state {
+                TabState::Tab => KeyContext::Nodes,
+                TabState::ReadNodeData => KeyContext::NodeData,
+                TabState::EditCreateNodeMode | TabState::EditCreateNodeAcl => KeyContext::Selector,
+                TabState::ImportPreview | TabState::CopyPreview => KeyContext::Preview,
+                TabState::Clusters
+                | TabState::Bookmarks
+                | TabState::SearchResults
+                | TabState::DiffView => KeyContext::List,
+                TabState::EditAcl => KeyContext::AclEditor,
+                TabState::DeletingNodes => KeyContext::Deleting,
+                TabState::EditCreateNodePath
+                | TabState::EditCreateNodeData
+                | TabState::EditCreateNodeTtl
+                | TabState::EditCreateNodeCredentials
+                | TabState::EditNodeData
+                | TabState::DeleteNode
+                | TabState::ConfirmDelete
+                | TabState::EditAclEntry
+                | TabState::ExportSubtree
+                | TabState::ImportSnapshot
+                | TabState::EditDiffLeft
+                | TabState::EditDiffRight
+                | TabState::EditDiffConnection
+                | TabState::EditDiffAuth
+                | TabState::FilterNodes
+                | TabState::SearchInput
+                | TabState::GoToPath
+                | TabState::EditBookmarkName
+                | TabState::RenameTab => KeyContext::Input,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{app::cluster::Cluster, tab::Tab};
+
+    #[test]
+    fn default_bindings() {
+        let keys = KeyMap::default();
+        assert_eq!(
+            keys.action(KeyContext::Nodes, KeyCode::Char('j')),
+            Some(Action::MoveDown)
+        );
+        assert_eq!(
+            keys.action(KeyContext::Nodes, KeyCode::Char('1')),
+            Some(Action::Select(0))
+        );
+        assert_eq!(
+            keys.action(KeyContext::Input, KeyCode::Esc),
+            Some(Action::Back)
+        );
+        assert_eq!(keys.action(KeyContext::Input, KeyCode::Char('j')), None);
+        assert_eq!(
+            keys.action(KeyContext::Connection, KeyCode::Esc),
+            Some(Action::Quit)
+        );
+        assert_eq!(
+            keys.keys(KeyContext::Nodes, Action::Select(0)).unwrap(),
+            "1-9"
+        );
+        assert_eq!(
+            keys.hints(
+                KeyContext::Preview,
+                &[(Action::Back, "cancel"), (Action::NewTab, "x")]
+            ),
+            "Esc to cancel"
+        );
+    }
+
+    #[test]
+    fn default_bindings_have_no_conflicts() {
+        for (context, bindings) in KeyMap::default().bindings {
+            for (i, (code, action)) in bindings.iter().enumerate() {
+                assert!(
+                    bindings[..i].iter().all(|(bound, _)| bound != code),
+                    "{} bound twice in {context}, last to {action}",
+                    key_name(*code)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn parses_key_names() {
+        assert_eq!(parse_key("j"), Some(KeyCode::Char('j')));
+        assert_eq!(parse_key("F"), Some(KeyCode::Char('F')));
+        assert_eq!(parse_key("é"), Some(KeyCode::Char('é')));
+        assert_eq!(parse_key("Esc"), Some(KeyCode::Esc));
+        assert_eq!(parse_key("pagedown"), Some(KeyCode::PageDown));
+        assert_eq!(parse_key("Space"), Some(KeyCode::Char(' ')));
+        assert_eq!(parse_key("F12"), Some(KeyCode::F(12)));
+        assert_eq!(parse_key(""), None);
+        assert_eq!(parse_key("Fx"), None);
+        assert_eq!(parse_key("ctrl-c"), None);
+    }
+
+    fn overrides(context: KeyContext, action: &str, keys: &[&str]) -> KeyOverrides {
+        let keys = keys.iter().map(|key| key.to_string()).collect();
+        KeyOverrides::from([(context, BTreeMap::from([(action.to_owned(), keys)]))])
+    }
+
+    fn problems(overrides: &KeyOverrides) -> String {
+        match KeyMap::from_config(overrides) {
+            Err(AppError::InvalidKeyBindings(problems)) => problems,
+            res => panic!("unexpected {:?}", res.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn remaps_actions() {
+        let keys = KeyMap::from_config(&overrides(KeyContext::Nodes, "move-down", &["n", "Down"]))
+            .unwrap();
+        assert_eq!(
+            keys.action(KeyContext::Nodes, KeyCode::Char('n')),
+            Some(Action::MoveDown)
+        );
+        assert_eq!(
+            keys.action(KeyContext::Nodes, KeyCode::Down),
+            Some(Action::MoveDown)
+        );
+        assert_eq!(keys.action(KeyContext::Nodes, KeyCode::Char('j')), None);
+        assert_eq!(
+            keys.action(KeyContext::List, KeyCode::Char('j')),
+            Some(Action::MoveDown)
+        );
+    }
+
+    #[test]
+    fn reports_invalid_bindings() {
+        assert_eq!(
+            problems(&overrides(KeyContext::Nodes, "fly", &["f"])),
+            "unknown action fly in nodes"
+        );
+        assert_eq!(
+            problems(&overrides(KeyContext::Nodes, "move-down", &["Hyper"])),
+            "unknown key Hyper for move-down in nodes"
+        );
+        assert_eq!(
+            problems(&overrides(KeyContext::Nodes, "move-down", &["k"])),
+            "k is bound to both move-up and move-down in nodes"
+        );
+        assert_eq!(
+            problems(&overrides(KeyContext::Input, "confirm", &["Enter", "y"])),
+            "y is bound to confirm in input and could not be typed"
+        );
+    }
+
+    #[test]
+    fn maps_screens_to_contexts() {
+        let mut app = App {
+            tabs: vec![Tab::default()],
+            clusters: vec![Cluster::default()],
+            ..Default::default()
+        };
+        let mut context = |state, tab_state| {
+            app.state = state;
+            app.curr_tab_mut().state = tab_state;
+            app.key_context()
+        };
+        let cases = [
+            (
+                AppState::EstablishingConnection,
+                TabState::Tab,
+                KeyContext::Connection,
+            ),
+            (
+                AppState::ConnectionFailed,
+                TabState::Tab,
+                KeyContext::ConnectionFailed,
+            ),
+            (AppState::EditingAuth, TabState::Tab, KeyContext::Input),
+            (
+                AppState::ConfirmDeleteProfile,
+                TabState::Tab,
+                KeyContext::Preview,
+            ),
+            (AppState::Tab, TabState::Tab, KeyContext::Nodes),
+            (AppState::Tab, TabState::ReadNodeData, KeyContext::NodeData),
+            (
+                AppState::Tab,
+                TabState::EditCreateNodeAcl,
+                KeyContext::Selector,
+            ),
+            (AppState::Tab, TabState::CopyPreview, KeyContext::Preview),
+            (AppState::Tab, TabState::DiffView, KeyContext::List),
+            (AppState::Tab, TabState::EditAcl, KeyContext::AclEditor),
+            (AppState::Tab, TabState::EditAclEntry, KeyContext::Input),
+            (AppState::Tab, TabState::FilterNodes, KeyContext::Input),
+            (AppState::Tab, TabState::DeletingNodes, KeyContext::Deleting),
+        ];
+        for (state, tab_state, expected) in cases {
+            assert_eq!(
+                context(state, tab_state),
+                expected,
+                "{state:?} {tab_state:?}"
+            );
+        }
+    }
+}
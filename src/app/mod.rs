@@ -1,42 +1,180 @@
+pub mod acl_editor;
+pub mod action;
+pub mod bookmarks;
+pub mod cluster;
 pub mod connection;
+pub mod diff;
+pub mod environment;
+pub mod events;
+pub mod export;
+pub mod history;
+pub mod import;
+pub mod keymap;
+pub mod mode;
 pub mod navigation;
+pub mod profiles;
+pub mod recursive_delete;
 pub mod render;
+pub mod search;
+pub mod session;
+pub mod split;
 pub mod state;
+pub mod tabs;
+pub mod tree;
+pub mod watch;
 pub mod zk_ops;
-use std::mem;
+use std::{mem, path::PathBuf, time::Duration};
 
-use crate::{node_data::NodeData, tab::Tab};
+use crate::{
+    cli::CliArgs,
+    config::{default_config_path, Config},
+    events::EventSender,
+    node_data::NodeData,
+    tab::Tab,
+    zk::WatchChannel,
+    zk_path::ZkPath,
+};
+use cluster::Cluster;
 use connection::Connection;
+use keymap::KeyMap;
+use mode::AppMode;
 use ratatui::{
-    style::{Style, Stylize},
+    style::{palette::tailwind, Style, Stylize},
     text::Line,
     widgets::{List, ListState, Tabs},
 };
+use split::Split;
 use state::AppState;
+use zk_ops::{DEFAULT_CONNECT_TIMEOUT, DEFAULT_SESSION_TIMEOUT};
 use zookeeper_async::Stat;
 
-pub const BASE_RESOURCE: &str = "/";
 pub const CONFIRMATION_STRING: &str = "DELETE";
 #[derive(Default)]
 pub struct App {
     pub state: AppState,
+    pub mode: AppMode,
+    pub mode_locked: bool, // mode was forced from the command line
     pub connection: Option<Connection>,
-    pub zk: Option<zookeeper_async::ZooKeeper>, //TODO: Get rid from Option
     pub connection_input: String,
+    pub auth_input: String,
+    pub connection_error: Option<String>,
+    pub connection_failure: Option<String>,
+    pub connection_attempts: u32,
+    pub session_timeout: Duration,
+    pub connect_timeout: Duration,
+    pub start_path: Option<String>,
+    pub config: Config,
+    pub config_path: PathBuf,
+    pub config_load_failed: bool, // the config file is never overwritten then
+    pub keymap: KeyMap,
+    pub profile_list_state: ListState,
+    pub profile_name_input: String,
+    pub bookmark_list_state: ListState,
+    pub cluster_list_state: ListState,
+    pub clusters: Vec<Cluster>, // tabs refer to their cluster by index
     pub curr_tab: usize,
     pub tabs: Vec<Tab>,
+    pub split: Option<Split>,
+    pub event_tx: Option<EventSender>, // set by the event loop, watch events go through it
+}
+
+/// Digest credentials with the password hidden.
+pub fn masked_auth(auth: &str) -> String {
+    match auth.split_once(':') {
+        Some((user, password)) => format!("{user}:{}", "*".repeat(password.len())),
+        None => auth.to_owned(),
+    }
 }
 
 impl App {
-    pub fn new(connection: Connection) -> Self {
-        Self {
-            connection_input: connection.to_string(),
-            connection: Some(connection),
-            tabs: vec![Tab::default(), Tab::default(), Tab::default()],
+    pub fn new(cli: CliArgs) -> Self {
+        let config_path = cli.config.unwrap_or_else(default_config_path);
+        let (config, config_error) = match Config::load(&config_path) {
+            Ok(config) => (config, None),
+            Err(e) => (
+                Config::default(),
+                Some(format!("Failed to load {} : {e}", config_path.display())),
+            ),
+        };
+
+        let (keymap, keymap_error) = match KeyMap::from_config(&config.keys) {
+            Ok(keymap) => (keymap, None),
+            Err(e) => (
+                KeyMap::default(),
+                Some(format!(
+                    "{e} in {}, using the default keys",
+                    config_path.display()
+                )),
+            ),
+        };
+
+        let mut app = Self {
+            mode: cli.mode.unwrap_or_default(),
+            mode_locked: cli.mode.is_some(),
+            connection_input: cli.connect,
+            auth_input: cli.auth.unwrap_or_default(),
+            session_timeout: cli
+                .session_timeout
+                .map(Duration::from_millis)
+                .unwrap_or(DEFAULT_SESSION_TIMEOUT),
+            connect_timeout: cli
+                .connect_timeout
+                .map(Duration::from_millis)
+                .unwrap_or(DEFAULT_CONNECT_TIMEOUT),
+            config,
+            config_path,
+            config_load_failed: config_error.is_some(),
+            keymap,
+            clusters: vec![Cluster::default()],
+            tabs: vec![Tab::default()],
             curr_tab: 0usize,
             ..Default::default()
+        };
+        let profile = cli.profile.map(|name| {
+            let index = app.config.profiles.iter().position(|p| p.name == name);
+            (name, index)
+        });
+        if let Some((_, Some(index))) = profile {
+            app.select_profile(index);
+        }
+        app.parse_connection();
+        // subcommands refuse to run without a connection instead of using the default one
+        if let Some((name, None)) = profile {
+            app.connection = None;
+            app.connection_error = Some(format!(
+                "Unknown profile {name} in {}",
+                app.config_path.display()
+            ));
+        }
+        let errors = [app.connection_error.take(), config_error, keymap_error]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+        app.connection_error = Some(errors.join("\n")).filter(|errors| !errors.is_empty());
+        app
+    }
+
+    /// Validates `connection_input`, storing either the parsed connection or the error to show.
+    pub fn parse_connection(&mut self) -> bool {
+        match self.connection_input.parse::<Connection>() {
+            Ok(connection) => {
+                self.connection_error = None;
+                self.connection = Some(connection);
+            }
+            Err(e) => {
+                self.connection_error = Some(e.to_string());
+                self.connection = None;
+            }
         }
+        self.connection.is_some()
     }
+
+    pub fn submit_connection_input(&mut self) {
+        if self.parse_connection() {
+            self.state = AppState::EstablishingConnection;
+        }
+    }
+
     pub fn connection_str(&self) -> String {
         match self.connection {
             Some(ref conn) => conn.to_string(),
@@ -44,15 +182,34 @@ impl App {
         }
     }
 
-    pub fn tabs_titles(&self) -> impl Iterator<Item = Line> {
-        self.tabs.iter().map(|t| t.title())
+    /// Auth input with the password hidden, e.g. `admin:*****`.
+    pub fn masked_auth_input(&self) -> String {
+        masked_auth(&self.auth_input)
+    }
+
+    pub fn tabs_titles(&self) -> impl Iterator<Item = Line<'_>> {
+        self.tabs
+            .iter()
+            .enumerate()
+            .map(|(index, t)| t.title(index, &self.clusters[t.cluster]))
+    }
+
+    /// Cluster and application mode of the current tab.
+    pub fn mode_title(&self) -> Line<'static> {
+        let mode = self.cluster().mode;
+        Line::from(vec![
+            self.cluster().title(),
+            format!("  {mode}  ")
+                .fg(tailwind::SLATE.c200)
+                .bg(mode.color()),
+        ])
     }
 
     pub fn curr_tab_index(&self) -> usize {
         self.curr_tab
     }
 
-    pub fn tabs(&self) -> Tabs {
+    pub fn tabs(&self) -> Tabs<'_> {
         Tabs::new(self.tabs_titles())
             .highlight_style(Tab::highlite_style())
             .select(self.curr_tab_index())
@@ -86,6 +243,20 @@ impl App {
         &mut self.tabs[self.curr_tab]
     }
 
+    /// Watch channel tagging the events of the cluster at `index`.
+    pub(crate) fn watch_channel(&self, index: usize) -> WatchChannel {
+        WatchChannel::new(index, self.event_tx.clone())
+    }
+
+    /// Cluster the current tab is connected to.
+    pub fn cluster(&self) -> &Cluster {
+        &self.clusters[self.curr_tab().cluster]
+    }
+    pub fn cluster_mut(&mut self) -> &mut Cluster {
+        let cluster = self.curr_tab().cluster;
+        &mut self.clusters[cluster]
+    }
+
     pub fn tab_list_state(&self) -> &ListState {
         &self.curr_tab().list_state
     }
@@ -94,30 +265,18 @@ impl App {
         &self.curr_tab().tab_data
     }
 
-    pub fn selected_resource(&self) -> Option<String> {
-        let selected_offset = self.tab_list_state().selected();
-        match selected_offset {
-            Some(offset) => self
-                .tab_data()
-                .get(offset)
-                .cloned()
-                .map(|p| format!("/{p}")),
-            None => None,
-        }
-    }
-
-    pub(crate) fn tab_full_resource_path(&self) -> String {
-        let prev = &self.curr_tab().prev_resources;
-        let curr = &self.curr_tab().curr_resource;
-        [
-            prev.concat(),
-            curr.clone().unwrap_or(BASE_RESOURCE.to_owned()),
-        ]
-        .concat()
+    /// Path of the node selected in the list, the listed node itself when nothing is selected.
+    pub fn selected_path(&self) -> ZkPath {
+        let listing_path = &self.curr_tab().listing_path;
+        self.tab_list_state()
+            .selected()
+            .and_then(|offset| self.tab_data().get(offset))
+            .and_then(|name| listing_path.join(name).ok())
+            .unwrap_or_else(|| listing_path.clone())
     }
 
-    pub(crate) fn is_full_resources_path_empty(&self) -> bool {
-        self.curr_tab().prev_resources.is_empty() && self.curr_tab().curr_resource.is_none()
+    pub(crate) fn tab_path(&self) -> ZkPath {
+        self.curr_tab().node_path.clone()
     }
 
     pub(crate) fn set_tab_message(&mut self, msg: String) {
@@ -132,11 +291,29 @@ impl App {
         self.curr_tab_mut().message.clear();
     }
 
+    pub(crate) fn ensure_can_write(&mut self) -> bool {
+        let mode = self.cluster().mode;
+        if !mode.can_write() {
+            self.set_tab_message(format!("Modifying nodes is forbidden in {mode} mode"));
+            return false;
+        }
+        true
+    }
+
+    pub(crate) fn ensure_can_delete(&mut self) -> bool {
+        let mode = self.cluster().mode;
+        if !mode.can_delete() {
+            self.set_tab_message(format!("Deleting nodes is forbidden in {mode} mode"));
+            return false;
+        }
+        true
+    }
+
     pub(crate) fn is_deletion_confirmed(&mut self) -> bool {
         let confirmation = mem::take(&mut self.curr_tab_mut().input_buf);
         confirmation.eq(CONFIRMATION_STRING)
     }
-    pub fn stat_list(&self) -> List {
+    pub fn stat_list(&self) -> List<'_> {
         let Some(ref stat) = self.curr_tab().current_node_stat else {
             return List::new(Vec::<Vec<Line>>::new());
         };
@@ -0,0 +1,45 @@
+use clap::ValueEnum;
+use core::fmt;
+use ratatui::style::{palette::tailwind, Color};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AppMode {
+    /// All operations are allowed
+    #[default]
+    ReadWrite,
+    /// Only read operations are allowed
+    ReadOnly,
+    /// Node deletion is forbidden
+    NoDelete,
+}
+
+impl AppMode {
+    pub fn can_write(&self) -> bool {
+        matches!(self, AppMode::ReadWrite | AppMode::NoDelete)
+    }
+
+    pub fn can_delete(&self) -> bool {
+        matches!(self, AppMode::ReadWrite)
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            AppMode::ReadWrite => tailwind::RED.c700,
+            AppMode::ReadOnly => tailwind::GREEN.c700,
+            AppMode::NoDelete => tailwind::AMBER.c700,
+        }
+    }
+}
+
+impl fmt::Display for AppMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            AppMode::ReadWrite => "Read-Write",
+            AppMode::ReadOnly => "Read-Only",
+            AppMode::NoDelete => "No-Delete",
+        };
+        write!(f, "{name}")
+    }
+}
@@ -1,9 +1,11 @@
-use std::cmp::min;
-
-use super::App;
+use super::{state::TabState, App};
+use crate::{errors::zk_error_message, zk_path::ZkPath};
 
 impl App {
     pub fn next(&mut self) {
+        if self.curr_tab().tab_data.is_empty() {
+            return;
+        }
         let i = match self.curr_tab().list_state.selected() {
             Some(i) => {
                 if i >= self.curr_tab().tab_data.len() - 1 {
@@ -17,6 +19,9 @@ impl App {
         self.curr_tab_mut().list_state.select(Some(i));
     }
     pub fn previous(&mut self) {
+        if self.curr_tab().tab_data.is_empty() {
+            return;
+        }
         let i = match self.curr_tab().list_state.selected() {
             Some(i) => {
                 if i == 0 {
@@ -29,17 +34,193 @@ impl App {
         };
         self.curr_tab_mut().list_state.select(Some(i));
     }
-    fn tabs_len(&self) -> usize {
-        self.tabs.len()
+    /// Points the tab at the node selected in the list.
+    pub(crate) fn sync_list_selection(&mut self) {
+        self.curr_tab_mut().node_path = self.selected_path();
+    }
+
+    /// Lists the children of the selected node.
+    pub(crate) async fn enter_node(&mut self) {
+        let path = self.tab_path();
+        if self.store_curr_tab_children_by_path(&path).await {
+            self.curr_tab_mut().history.visit(path);
+            self.sync_list_selection();
+        }
+    }
+
+    /// Lists the parent of the listed node, with the listed node selected.
+    pub(crate) async fn leave_node(&mut self) {
+        let listing_path = self.curr_tab().listing_path.clone();
+        if listing_path.is_root() {
+            return;
+        }
+        self.record_visit();
+        self.curr_tab_mut().node_path = listing_path;
+        self.open_flat_list().await;
+    }
+
+    /// Lists the listed node again, keeping the selection in place as far as possible.
+    pub(crate) async fn reload_listing(&mut self) {
+        let path = self.curr_tab().listing_path.clone();
+        let Some(children) = self.get_children(&path).await else {
+            return;
+        };
+        if self.curr_tab().unfiltered_data.is_some() {
+            self.curr_tab_mut().unfiltered_data = Some(children);
+            self.apply_filter();
+            return;
+        }
+        let last = children.len().saturating_sub(1);
+        let selected = self.tab_list_state().selected().map(|i| i.min(last));
+
+        self.curr_tab_mut().tab_data = children;
+        self.curr_tab_mut().list_state.select(selected);
+        self.sync_list_selection();
+    }
+
+    /// Jumps to `path`, remembering the current node in the history.
+    pub(crate) async fn open_path(&mut self, path: &ZkPath) {
+        self.record_visit();
+        self.show_path(path).await;
+    }
+
+    /// Shows `path` selected in its parent listing, or in the tree in the tree mode.
+    pub(crate) async fn show_path(&mut self, path: &ZkPath) {
+        self.curr_tab_mut().node_path = path.clone();
+        if self.curr_tab().tree_mode {
+            self.open_tree().await;
+        } else {
+            self.open_flat_list().await;
+        }
+        self.auto_load_stat().await;
+    }
+
+    /// Lists the current tab again, or rebuilds its tree in the tree mode.
+    pub(crate) async fn reload_tab(&mut self) {
+        if self.curr_tab().tree_mode {
+            self.open_tree().await;
+        } else {
+            self.reload_listing().await;
+        }
+        self.auto_load_stat().await;
+    }
+
+    pub(crate) async fn auto_load_stat(&mut self) {
+        if self.curr_tab().toggle_stats_auto_load {
+            self.store_node_stat().await;
+        }
+    }
+
+    pub(crate) fn open_go_to_path(&mut self) {
+        let path = self.tab_path();
+        let tab = self.curr_tab_mut();
+        tab.input_buf = path.to_string();
+        tab.path_completions.clear();
+        tab.state = TabState::GoToPath;
     }
-    fn current_tab(&self) -> usize {
-        self.curr_tab
+
+    /// Completes the last node name of `input_buf` from the children of its parent node.
+    pub(crate) async fn complete_path(&mut self) {
+        let input = self.curr_tab().input_buf.clone();
+        let (parent, prefix) = match input.rsplit_once('/') {
+            Some(("", prefix)) => (ZkPath::root(), prefix),
+            Some((parent, prefix)) => match ZkPath::parse(parent) {
+                Ok(parent) => (parent, prefix),
+                Err(e) => {
+                    self.set_tab_message(e.to_string());
+                    return;
+                }
+            },
+            None => return,
+        };
+        let Some(ref zk) = self.cluster().zk else {
+            "Failed to get zookeeper client".clone_into(&mut self.curr_tab_mut().message);
+            return;
+        };
+
+        let mut completions = match zk.get_children(&parent, false).await {
+            Ok(children) => children
+                .into_iter()
+                .filter(|child| child.starts_with(prefix))
+                .collect::<Vec<_>>(),
+            Err(e) => {
+                self.set_tab_message(zk_error_message("list children of", &parent, &e));
+                return;
+            }
+        };
+        completions.sort_unstable();
+
+        let tab = self.curr_tab_mut();
+        match completions.as_slice() {
+            [] => tab.message = format!("No nodes under {parent} start with {prefix}"),
+            [name] => match parent.join(name) {
+                Ok(path) => tab.input_buf = format!("{path}/"),
+                Err(e) => tab.message = e.to_string(),
+            },
+            [first, rest @ ..] => {
+                let common = rest.iter().fold(first.as_str(), |common, name| {
+                    let len = common
+                        .char_indices()
+                        .zip(name.chars())
+                        .take_while(|((_, a), b)| a == b)
+                        .last()
+                        .map_or(0, |((i, a), _)| i + a.len_utf8());
+                    &common[..len]
+                });
+                tab.input_buf = parent
+                    .join(common)
+                    .unwrap_or_else(|_| parent.clone())
+                    .to_string();
+                if common.is_empty() && !parent.is_root() {
+                    tab.input_buf.push('/');
+                }
+            }
+        }
+        tab.path_completions = completions;
     }
-    pub fn next_tab(&mut self) {
-        self.curr_tab = min(self.current_tab() + 1, self.tabs_len() - 1);
+
+    /// Opens the node typed in `input_buf` in one step.
+    pub(crate) async fn go_to_path(&mut self) {
+        let input = self.curr_tab().input_buf.trim();
+        let input = match input.strip_suffix('/') {
+            Some(trimmed) if !trimmed.is_empty() => trimmed,
+            _ => input,
+        };
+        let path = match ZkPath::parse(input) {
+            Ok(path) => path,
+            Err(e) => {
+                self.set_tab_message(e.to_string());
+                return;
+            }
+        };
+        if self.open_existing_path(&path).await {
+            let tab = self.curr_tab_mut();
+            tab.input_buf.clear();
+            tab.path_completions.clear();
+            tab.state = TabState::Tab;
+        }
     }
 
-    pub fn previous_tab(&mut self) {
-        self.curr_tab = self.current_tab().saturating_sub(1);
+    /// Jumps to `path` when the node exists, reporting a missing node in the tab message.
+    pub(crate) async fn open_existing_path(&mut self, path: &ZkPath) -> bool {
+        let Some(ref zk) = self.cluster().zk else {
+            "Failed to get zookeeper client".clone_into(&mut self.curr_tab_mut().message);
+            return false;
+        };
+
+        match zk.exists(path, false).await {
+            Ok(Some(_)) => {
+                self.open_path(path).await;
+                true
+            }
+            Ok(None) => {
+                self.set_tab_message(format!("Node {path} does not exist"));
+                false
+            }
+            Err(e) => {
+                self.set_tab_message(zk_error_message("read stat of", path, &e));
+                false
+            }
+        }
     }
 }
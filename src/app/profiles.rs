@@ -0,0 +1,153 @@
+use super::{
+    state::AppState,
+    zk_ops::{DEFAULT_CONNECT_TIMEOUT, DEFAULT_SESSION_TIMEOUT},
+    App,
+};
+use crate::{config::Profile, zk_path::ZkPath};
+use std::{mem, time::Duration};
+
+impl App {
+    pub(crate) fn selected_profile(&self) -> Option<&Profile> {
+        self.profile_list_state
+            .selected()
+            .and_then(|i| self.config.profiles.get(i))
+    }
+
+    pub(crate) fn next_profile(&mut self) {
+        let len = self.config.profiles.len();
+        if len == 0 {
+            return;
+        }
+        let i = match self.profile_list_state.selected() {
+            Some(i) if i + 1 < len => i + 1,
+            _ => 0,
+        };
+        self.select_profile(i);
+    }
+
+    pub(crate) fn previous_profile(&mut self) {
+        let len = self.config.profiles.len();
+        if len == 0 {
+            return;
+        }
+        let i = match self.profile_list_state.selected() {
+            Some(0) | None => len - 1,
+            Some(i) => i - 1,
+        };
+        self.select_profile(i);
+    }
+
+    /// Fills the connection inputs from the profile at `index`.
+    pub(crate) fn select_profile(&mut self, index: usize) {
+        let Some(profile) = self.config.profiles.get(index).cloned() else {
+            return;
+        };
+        self.profile_list_state.select(Some(index));
+        self.connection_input = profile.connect;
+        self.auth_input = profile.auth.unwrap_or_default();
+        self.session_timeout = profile
+            .session_timeout_ms
+            .map(Duration::from_millis)
+            .unwrap_or(DEFAULT_SESSION_TIMEOUT);
+        self.connect_timeout = profile
+            .connect_timeout_ms
+            .map(Duration::from_millis)
+            .unwrap_or(DEFAULT_CONNECT_TIMEOUT);
+        self.start_path = profile.start_path;
+        if !self.mode_locked {
+            self.mode = profile.mode.unwrap_or_default();
+        }
+        self.parse_connection();
+    }
+
+    /// Stores the current connection inputs as a profile named after `profile_name_input`.
+    pub(crate) fn save_profile(&mut self) {
+        let name = mem::take(&mut self.profile_name_input).trim().to_owned();
+        if name.is_empty() {
+            self.connection_error = Some("Profile name must not be empty".to_owned());
+            return;
+        }
+        self.state = AppState::EstablishingConnection;
+        if !self.parse_connection() {
+            return;
+        }
+
+        let existing = self.config.profile(&name).cloned().unwrap_or_default();
+        self.config.upsert_profile(Profile {
+            name: name.clone(),
+            connect: self.connection_str(),
+            auth: Some(self.auth_input.clone()).filter(|auth| !auth.is_empty()),
+            // a mode forced on the command line is not the profile's own
+            mode: if self.mode_locked {
+                existing.mode
+            } else {
+                Some(self.mode)
+            },
+            session_timeout_ms: Some(self.session_timeout)
+                .filter(|timeout| *timeout != DEFAULT_SESSION_TIMEOUT)
+                .map(|timeout| timeout.as_millis() as u64),
+            connect_timeout_ms: Some(self.connect_timeout)
+                .filter(|timeout| *timeout != DEFAULT_CONNECT_TIMEOUT)
+                .map(|timeout| timeout.as_millis() as u64),
+            ..existing
+        });
+        self.profile_list_state
+            .select(self.config.profiles.iter().position(|p| p.name == name));
+        self.persist_config();
+    }
+
+    /// Asks to confirm the deletion of the selected profile.
+    pub(crate) fn confirm_delete_profile(&mut self) {
+        if self.selected_profile().is_some() {
+            self.state = AppState::ConfirmDeleteProfile;
+        }
+    }
+
+    pub(crate) fn delete_profile(&mut self) {
+        self.state = AppState::EstablishingConnection;
+        let Some(i) = self.profile_list_state.selected() else {
+            return;
+        };
+        if i >= self.config.profiles.len() {
+            return;
+        }
+        self.config.profiles.remove(i);
+        let selected = i.min(self.config.profiles.len().saturating_sub(1));
+        self.profile_list_state
+            .select(Some(selected).filter(|_| !self.config.profiles.is_empty()));
+        self.persist_config();
+    }
+
+    fn persist_config(&mut self) {
+        if let Err(e) = self.save_config() {
+            self.connection_error = Some(e);
+        }
+    }
+
+    /// Writes the config file, unless it failed to load and saving would drop its content.
+    pub(crate) fn save_config(&self) -> Result<(), String> {
+        if self.config_load_failed {
+            return Err(format!(
+                "Not saving {} : it failed to load",
+                self.config_path.display()
+            ));
+        }
+        self.config
+            .save(&self.config_path)
+            .map_err(|e| format!("Failed to save {} : {e}", self.config_path.display()))
+    }
+
+    /// Opens the profile start path in the current tab, falling back to the root node.
+    pub(crate) async fn open_start_path(&mut self) {
+        let start_path = self
+            .cluster()
+            .start_path
+            .as_deref()
+            .and_then(|path| ZkPath::parse(path).ok())
+            .unwrap_or_default();
+        if start_path.is_root() || !self.store_curr_tab_children_by_path(&start_path).await {
+            self.store_curr_tab_children_by_path(&ZkPath::root()).await;
+        }
+        self.sync_list_selection();
+    }
+}
@@ -0,0 +1,194 @@
+use super::{state::TabState, App};
+use crate::{errors::zk_error_message, zk_path::ZkPath};
+use zookeeper_async::{ZkError, ZooKeeper};
+
+pub const DELETE_BATCH_SIZE: usize = 16;
+pub const DELETE_PREVIEW_SIZE: usize = 10;
+
+#[derive(Debug, Default)]
+pub struct DeletePlan {
+    pub root: ZkPath,
+    pub nodes: Vec<ZkPath>, // children always go before their parent
+    pub deleted: usize,
+    pub processed: usize,
+    pub failures: Vec<(ZkPath, String)>,
+    pub stopped: bool, // cancelled before every node was processed
+}
+
+impl DeletePlan {
+    pub fn is_finished(&self) -> bool {
+        self.stopped || self.processed >= self.nodes.len()
+    }
+
+    pub fn preview(&self) -> impl Iterator<Item = &ZkPath> {
+        self.nodes.iter().rev().take(DELETE_PREVIEW_SIZE)
+    }
+
+    pub fn ratio(&self) -> f64 {
+        if self.nodes.is_empty() {
+            return 1.0;
+        }
+        self.processed as f64 / self.nodes.len() as f64
+    }
+
+    pub fn summary(&self) -> String {
+        let mut summary = String::new();
+        if self.processed < self.nodes.len() {
+            summary.push_str("Recursive delete cancelled\n");
+        }
+        summary.push_str(&format!(
+            "Deleted {} of {} nodes under {}\n",
+            self.deleted,
+            self.nodes.len(),
+            self.root
+        ));
+        for (path, err) in self.failures.iter() {
+            summary.push_str(&format!("Failed {path} : {err}\n"));
+        }
+        summary
+    }
+}
+
+/// Keeps the children listed for `path`, a node other than `root` deleted since its parent was
+/// listed is skipped with `None`.
+fn listed_children(
+    root: &ZkPath,
+    path: &ZkPath,
+    listed: Result<Vec<String>, ZkError>,
+) -> Result<Option<Vec<String>>, (ZkPath, ZkError)> {
+    match listed {
+        Ok(children) => Ok(Some(children)),
+        Err(ZkError::NoNode) if path != root => Ok(None),
+        Err(e) => Err((path.clone(), e)),
+    }
+}
+
+/// Lists `root` and all its descendants depth-first, parents before children in name order.
+pub(crate) async fn walk_subtree(
+    zk: &ZooKeeper,
+    root: &ZkPath,
+) -> Result<Vec<ZkPath>, (ZkPath, ZkError)> {
+    let mut visited = Vec::new();
+    let mut stack = vec![root.clone()];
+    while let Some(path) = stack.pop() {
+        let listed = zk.get_children(&path, false).await;
+        let Some(mut children) = listed_children(root, &path, listed)? else {
+            continue;
+        };
+        children.sort_unstable_by(|a, b| b.cmp(a));
+        for child in children.iter() {
+            let child = path
+                .join(child)
+                .map_err(|_| (path.clone(), ZkError::BadArguments))?;
+            stack.push(child);
+        }
+        visited.push(path);
+    }
+    Ok(visited)
+}
+
+impl App {
+    /// Walks the subtree under `node_path_buf` and stores the nodes to delete, leaves first.
+    pub(crate) async fn plan_recursive_delete(&mut self) -> bool {
+        if !self.ensure_can_delete() {
+            return false;
+        }
+        let Some(root) = self.parse_node_path_buf() else {
+            return false;
+        };
+        let Some(ref zk) = self.cluster().zk else {
+            "Failed to get zookeeper client".clone_into(&mut self.curr_tab_mut().message);
+            return false;
+        };
+
+        let mut visited = match walk_subtree(zk, &root).await {
+            Ok(visited) => visited,
+            Err((path, e)) => {
+                self.curr_tab_mut().message = zk_error_message("walk", &path, &e);
+                return false;
+            }
+        };
+        visited.reverse();
+
+        self.curr_tab_mut().delete_plan = Some(DeletePlan {
+            root,
+            nodes: visited,
+            ..Default::default()
+        });
+        true
+    }
+
+    pub(crate) async fn delete_next_batch(&mut self) {
+        if !self.ensure_can_delete() {
+            self.curr_tab_mut().delete_plan = None;
+            return;
+        }
+        let cluster = self.curr_tab().cluster;
+        let cluster = &mut self.clusters[cluster];
+        let Some(ref zk) = cluster.zk else {
+            "Failed to get zookeeper client".clone_into(&mut self.curr_tab_mut().message);
+            return;
+        };
+        let tab = &mut self.tabs[self.curr_tab];
+        let Some(ref mut plan) = tab.delete_plan else {
+            return;
+        };
+
+        let end = (plan.processed + DELETE_BATCH_SIZE).min(plan.nodes.len());
+        for path in plan.nodes[plan.processed..end].iter() {
+            match zk.delete(path, None).await {
+                Ok(_) => {
+                    plan.deleted += 1;
+                    cluster.own_changes.record(path, cluster.session_timeout);
+                }
+                Err(ZkError::NoAuth) => plan
+                    .failures
+                    .push((path.clone(), "not authorised".to_owned())),
+                Err(e) => plan.failures.push((path.clone(), e.to_string())),
+            }
+        }
+        plan.processed = end;
+    }
+
+    pub(crate) async fn finish_recursive_delete(&mut self) {
+        let plan = self.curr_tab_mut().delete_plan.take();
+        self.curr_tab_mut().state = TabState::Tab;
+        self.reload_listing().await;
+        if let Some(plan) = plan {
+            self.set_tab_message(plan.summary());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(path: &str) -> ZkPath {
+        path.parse().unwrap()
+    }
+
+    #[test]
+    fn vanished_child_is_skipped() {
+        let root = path("/app");
+        let child = path("/app/gone");
+        assert_eq!(
+            listed_children(&root, &child, Err(ZkError::NoNode)).unwrap(),
+            None
+        );
+        assert_eq!(
+            listed_children(&root, &child, Ok(vec!["a".to_owned()])).unwrap(),
+            Some(vec!["a".to_owned()])
+        );
+    }
+
+    #[test]
+    fn missing_root_and_other_errors_fail() {
+        let root = path("/app");
+        let (failed, e) = listed_children(&root, &root, Err(ZkError::NoNode)).unwrap_err();
+        assert_eq!((failed, e), (root.clone(), ZkError::NoNode));
+        let child = path("/app/locked");
+        let (failed, e) = listed_children(&root, &child, Err(ZkError::NoAuth)).unwrap_err();
+        assert_eq!((failed, e), (child, ZkError::NoAuth));
+    }
+}
@@ -1,16 +1,49 @@
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
-    style::{Modifier, Style, Stylize},
-    widgets::{Clear, List, ListItem, Paragraph, StatefulWidget, Widget, Wrap},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Clear, Gauge, List, ListItem, Paragraph, StatefulWidget, Widget, Wrap},
 };
 
-use crate::ui::ui_handle::AppUi;
+use crate::{
+    acl::acl_to_string,
+    diff::{DiffKind, DiffLine},
+    ui::ui_handle::AppUi,
+};
 
-use super::App;
+use super::{
+    masked_auth, recursive_delete::DELETE_PREVIEW_SIZE, search::SearchResult, state::TabState, App,
+};
 
 impl App {
+    /// Nodes list with the stat and ACL of the selected node.
+    pub(crate) fn render_work_space(&mut self, area: Rect, buf: &mut Buffer) {
+        let [nodes_list_rect, node_info_rect] = AppUi::work_space_layout().areas(area);
+        let [node_stat_rect, node_acl_rect] = AppUi::node_info_layout().areas(node_info_rect);
+
+        self.render_node_stat(node_stat_rect, buf);
+        self.render_node_acl(node_acl_rect, buf);
+        self.render_nodes_list(nodes_list_rect, buf);
+    }
+
+    /// Renders the tab of the unfocused pane dimmed.
+    pub(crate) fn render_other_pane(&mut self, area: Rect, buf: &mut Buffer) {
+        let Some(split) = self.split else {
+            return;
+        };
+        let curr_tab = self.curr_tab;
+        self.curr_tab = split.other;
+        self.render_work_space(area, buf);
+        self.curr_tab = curr_tab;
+        buf.set_style(area, Style::default().add_modifier(Modifier::DIM));
+    }
+
     pub(crate) fn render_nodes_list(&mut self, area: Rect, buf: &mut Buffer) {
+        if self.curr_tab().tree_mode {
+            self.render_nodes_tree(area, buf);
+            return;
+        }
         let curr_tab = self.curr_tab;
         let items: Vec<ListItem> = {
             let tab_data = &self.tabs[curr_tab].tab_data;
@@ -20,8 +53,15 @@ impl App {
                 .collect()
         };
 
+        let tab = self.curr_tab();
+        let filter = format!("filter: {} ({})", tab.filter_buf, tab.pattern_kind);
+        let block = if tab.state == TabState::FilterNodes {
+            AppUi::nodes_filter_block(filter, &self.keymap)
+        } else {
+            AppUi::nodes_block(tab.unfiltered_data.as_ref().map(|_| filter), &self.keymap)
+        };
         let list = List::new(items)
-            .block(AppUi::nodes_block())
+            .block(block)
             .highlight_style(Style::default().add_modifier(Modifier::ITALIC))
             .highlight_symbol(">>");
 
@@ -31,14 +71,64 @@ impl App {
         self.curr_tab_mut().list_state = list_state;
     }
 
+    fn render_nodes_tree(&mut self, area: Rect, buf: &mut Buffer) {
+        let tree = &mut self.tabs[self.curr_tab].tree;
+        let items: Vec<ListItem> = tree
+            .nodes
+            .iter()
+            .map(|node| {
+                Line::from(vec![
+                    Span::styled(node.guides.as_str(), Style::default().fg(Color::DarkGray)),
+                    Span::raw(node.marker()),
+                    Span::raw(node.name.as_str()),
+                ])
+                .into()
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(AppUi::nodes_tree_block(&self.keymap))
+            .highlight_style(Style::default().add_modifier(Modifier::ITALIC))
+            .highlight_symbol(">>");
+
+        StatefulWidget::render(list, area, buf, &mut tree.list_state);
+    }
+
+    pub(crate) fn render_profiles_list(&mut self, area: Rect, buf: &mut Buffer) {
+        let items: Vec<ListItem> = self
+            .config
+            .profiles
+            .iter()
+            .map(|profile| ListItem::new(format!("{} ({})", profile.name, profile.connect)))
+            .collect();
+
+        let list = List::new(items)
+            .block(AppUi::profiles_block(&self.keymap))
+            .highlight_style(Style::default().add_modifier(Modifier::ITALIC))
+            .highlight_symbol(">>");
+
+        StatefulWidget::render(list, area, buf, &mut self.profile_list_state);
+    }
+
     pub(crate) fn render_message_block(&mut self, area: Rect, buf: &mut Buffer) {
         Paragraph::new(self.curr_tab().message.to_string())
             .block(AppUi::message_block())
             .render(area, buf);
     }
 
+    pub(crate) fn render_status_bar(&mut self, area: Rect, buf: &mut Buffer) {
+        let session_title = self.session_title();
+        let [session_rect, connection_rect] =
+            AppUi::status_bar_layout(session_title.width() as u16).areas(area);
+        session_title.render(session_rect, buf);
+        Line::from(format!(" {}", self.cluster().connection)).render(connection_rect, buf);
+    }
+
     pub(crate) fn render_tabs(&mut self, area: Rect, buf: &mut Buffer) {
-        self.tabs().render(area, buf);
+        let mode_title = self.mode_title();
+        let [tabs_rect, mode_rect] = AppUi::tabs_bar_layout(mode_title.width() as u16).areas(area);
+        self.tabs().render(tabs_rect, buf);
+        mode_title.render(mode_rect, buf);
     }
 
     pub(crate) fn render_node_stat(&mut self, area: Rect, buf: &mut Buffer) {
@@ -58,7 +148,7 @@ impl App {
         Clear.render(area, buf);
         Paragraph::new(self.curr_tab().node_data.to_string())
             .wrap(Wrap { trim: true })
-            .block(AppUi::node_data_block())
+            .block(AppUi::node_data_block(&self.keymap))
             .render(area, buf);
     }
 
@@ -78,14 +168,105 @@ impl App {
     pub(crate) fn render_edit_data_non_active_block(&mut self, area: Rect, buf: &mut Buffer) {
         Paragraph::new(self.node_data_buf().as_str())
             .wrap(Wrap { trim: true })
-            .block(AppUi::edit_data_non_active_block())
+            .block(AppUi::edit_data_non_active_block(&self.keymap))
             .render(area, buf);
     }
 
     pub(crate) fn render_edit_data_active_block(&mut self, area: Rect, buf: &mut Buffer) {
         Paragraph::new(self.node_data_buf().as_str())
             .wrap(Wrap { trim: true })
-            .block(AppUi::edit_data_active_block())
+            .block(AppUi::edit_data_active_block(&self.keymap))
+            .render(area, buf);
+    }
+
+    pub(crate) fn render_create_mode_active_block(&mut self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new(format!("< {} >", self.curr_tab().create_mode))
+            .centered()
+            .block(AppUi::create_mode_active_block(&self.keymap))
+            .render(area, buf);
+    }
+
+    pub(crate) fn render_create_mode_non_active_block(&mut self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new(self.curr_tab().create_mode.to_string())
+            .centered()
+            .block(AppUi::create_mode_non_active_block())
+            .render(area, buf);
+    }
+
+    pub(crate) fn render_ttl_active_block(&mut self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new(self.curr_tab().ttl_buf.as_str())
+            .block(AppUi::ttl_active_block())
+            .render(area, buf);
+    }
+
+    pub(crate) fn render_ttl_non_active_block(&mut self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new(self.curr_tab().ttl_buf.as_str())
+            .block(AppUi::ttl_non_active_block())
+            .render(area, buf);
+    }
+
+    pub(crate) fn render_acl_preset_active_block(&mut self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new(format!("< {} >", self.curr_tab().acl_preset))
+            .centered()
+            .block(AppUi::acl_preset_active_block(&self.keymap))
+            .render(area, buf);
+    }
+
+    pub(crate) fn render_acl_preset_non_active_block(&mut self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new(self.curr_tab().acl_preset.to_string())
+            .centered()
+            .block(AppUi::acl_preset_non_active_block())
+            .render(area, buf);
+    }
+
+    pub(crate) fn render_credentials_active_block(&mut self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new(self.curr_tab().acl_credentials_buf.as_str())
+            .block(AppUi::credentials_active_block())
+            .render(area, buf);
+    }
+
+    pub(crate) fn render_credentials_non_active_block(&mut self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new(self.curr_tab().acl_credentials_buf.as_str())
+            .block(AppUi::credentials_non_active_block())
+            .render(area, buf);
+    }
+
+    pub(crate) fn render_node_acl(&mut self, area: Rect, buf: &mut Buffer) {
+        let items: Vec<ListItem> = match self.curr_tab().current_node_acl {
+            Some(ref acl) => acl
+                .iter()
+                .map(|entry| ListItem::new(acl_to_string(entry)))
+                .collect(),
+            None => Vec::new(),
+        };
+        Clear.render(area, buf);
+        Widget::render(List::new(items).block(AppUi::acl_block()), area, buf);
+    }
+
+    pub(crate) fn render_acl_editor(&mut self, area: Rect, buf: &mut Buffer) {
+        let items: Vec<ListItem> = self
+            .curr_tab()
+            .acl_buf
+            .iter()
+            .map(|entry| ListItem::new(acl_to_string(entry)))
+            .collect();
+
+        let list = List::new(items)
+            .block(AppUi::acl_editor_block(&self.keymap))
+            .highlight_style(Style::default().add_modifier(Modifier::ITALIC))
+            .highlight_symbol(">>");
+
+        let mut list_state = self.curr_tab().acl_list_state.clone();
+
+        Clear.render(area, buf);
+        StatefulWidget::render(list, area, buf, &mut list_state);
+        self.curr_tab_mut().acl_list_state = list_state;
+    }
+
+    pub(crate) fn render_acl_entry_input(&mut self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+        Paragraph::new(self.input_buf().as_str())
+            .block(AppUi::acl_entry_block(&self.keymap))
             .render(area, buf);
     }
 
@@ -99,17 +280,292 @@ impl App {
     pub(crate) fn render_edited_node_data(&mut self, area: Rect, buf: &mut Buffer) {
         Paragraph::new(self.node_data_buf().to_string())
             .wrap(Wrap { trim: true })
-            .block(AppUi::edited_data_block())
+            .block(AppUi::edited_data_block(&self.keymap))
             .render(area, buf);
     }
 
     pub(crate) fn render_delete_node(&mut self, area: Rect, buf: &mut Buffer) {
+        let block = if self.curr_tab().recursive_delete {
+            AppUi::recursive_delete_node_block(&self.keymap)
+        } else {
+            AppUi::delete_node_block(&self.keymap)
+        };
         Paragraph::new(self.node_path_buf().as_str())
             .wrap(Wrap { trim: true })
-            .block(AppUi::delete_node_block())
+            .block(block)
             .render(area, buf);
     }
 
+    pub(crate) fn render_export(&mut self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+        Paragraph::new(self.input_buf().as_str())
+            .wrap(Wrap { trim: true })
+            .block(AppUi::export_block(self.node_path_buf(), &self.keymap))
+            .render(area, buf);
+    }
+
+    pub(crate) fn render_import(&mut self, area: Rect, buf: &mut Buffer) {
+        let lines = vec![
+            Line::from(format!("File : {}", self.input_buf())),
+            Line::from(format!("Strategy : {}", self.curr_tab().import_strategy)),
+        ];
+        Clear.render(area, buf);
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: true })
+            .block(AppUi::import_block(self.node_path_buf(), &self.keymap))
+            .render(area, buf);
+    }
+
+    pub(crate) fn render_import_preview(&mut self, area: Rect, buf: &mut Buffer) {
+        let Some(ref plan) = self.curr_tab().import_plan else {
+            return;
+        };
+        let title = match self.curr_tab().state {
+            TabState::CopyPreview => "Copy Preview",
+            _ => "Import Preview",
+        };
+        let summary = plan.summary();
+        let mut lines: Vec<Line> = summary.lines().map(Line::from).collect();
+        lines.push(Line::from(""));
+        lines.extend(plan.lines().map(Line::from));
+
+        Clear.render(area, buf);
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: true })
+            .block(AppUi::import_preview_block(
+                title,
+                plan.has_conflicts(),
+                &self.keymap,
+            ))
+            .render(area, buf);
+    }
+
+    pub(crate) fn render_edit_diff(&mut self, area: Rect, buf: &mut Buffer) {
+        let block = AppUi::diff_popup_block(&self.keymap);
+        let [left_rect, right_rect, connection_rect, auth_rect, _] =
+            AppUi::diff_popup_layout().areas(block.inner(area));
+        let tab = self.curr_tab();
+        let auth = masked_auth(&tab.diff_auth_buf);
+        let fields = [
+            (
+                left_rect,
+                "Left path",
+                &tab.node_path_buf,
+                TabState::EditDiffLeft,
+            ),
+            (
+                right_rect,
+                "Right path",
+                &tab.input_buf,
+                TabState::EditDiffRight,
+            ),
+            (
+                connection_rect,
+                "Right connection (empty for the current one)",
+                &tab.diff_connection_buf,
+                TabState::EditDiffConnection,
+            ),
+            (
+                auth_rect,
+                "Right digest auth (user:password, empty for none)",
+                &auth,
+                TabState::EditDiffAuth,
+            ),
+        ];
+
+        Clear.render(area, buf);
+        block.render(area, buf);
+        for (rect, title, value, state) in fields {
+            let block = AppUi::diff_input_block(title);
+            let block = if tab.state == state {
+                block.on_blue()
+            } else {
+                block
+            };
+            Paragraph::new(value.as_str())
+                .block(block)
+                .render(rect, buf);
+        }
+    }
+
+    pub(crate) fn render_diff(&mut self, area: Rect, buf: &mut Buffer) {
+        let Some(ref diff) = self.curr_tab().diff else {
+            return;
+        };
+        let lines: Vec<Line> = diff
+            .lines()
+            .into_iter()
+            .map(|line| {
+                let style = match line {
+                    DiffLine::Node(DiffKind::Added, _) | DiffLine::Added(_) => {
+                        Style::default().fg(Color::Green)
+                    }
+                    DiffLine::Node(DiffKind::Removed, _) | DiffLine::Removed(_) => {
+                        Style::default().fg(Color::Red)
+                    }
+                    DiffLine::Node(DiffKind::Changed, _) => Style::default().fg(Color::Yellow),
+                    DiffLine::Same(_) => Style::default(),
+                };
+                Line::styled(line.to_string(), style)
+            })
+            .collect();
+
+        Clear.render(area, buf);
+        Paragraph::new(lines)
+            .block(AppUi::diff_block(diff.summary(), &self.keymap))
+            .scroll((self.curr_tab().diff_scroll, 0))
+            .render(area, buf);
+    }
+
+    pub(crate) fn render_search_input(&mut self, area: Rect, buf: &mut Buffer) {
+        let tab = self.curr_tab();
+        Clear.render(area, buf);
+        Paragraph::new(tab.input_buf.as_str())
+            .wrap(Wrap { trim: true })
+            .block(AppUi::search_block(
+                tab.search_kind,
+                &tab.search_root,
+                tab.pattern_kind,
+                &self.keymap,
+            ))
+            .render(area, buf);
+    }
+
+    pub(crate) fn render_go_to_path(&mut self, area: Rect, buf: &mut Buffer) {
+        let tab = self.curr_tab();
+        let mut lines = vec![Line::from(tab.input_buf.as_str()), Line::from("")];
+        lines.extend(
+            tab.path_completions
+                .iter()
+                .map(|name| Line::styled(name.as_str(), Style::default().fg(Color::DarkGray))),
+        );
+
+        Clear.render(area, buf);
+        Paragraph::new(lines)
+            .block(AppUi::go_to_path_block(&self.keymap))
+            .render(area, buf);
+    }
+
+    pub(crate) fn render_bookmark_name(&mut self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+        Paragraph::new(self.input_buf().as_str())
+            .wrap(Wrap { trim: true })
+            .block(AppUi::bookmark_name_block(&self.tab_path(), &self.keymap))
+            .render(area, buf);
+    }
+
+    pub(crate) fn render_rename_tab(&mut self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+        Paragraph::new(self.input_buf().as_str())
+            .wrap(Wrap { trim: true })
+            .block(AppUi::rename_tab_block(&self.keymap))
+            .render(area, buf);
+    }
+
+    pub(crate) fn render_bookmarks(&mut self, area: Rect, buf: &mut Buffer) {
+        let items: Vec<ListItem> = self
+            .config
+            .bookmarks
+            .iter()
+            .enumerate()
+            .map(|(i, bookmark)| {
+                let key = if i < 9 {
+                    format!("{} ", i + 1)
+                } else {
+                    "  ".to_owned()
+                };
+                Line::from(vec![
+                    Span::styled(key, Style::default().fg(Color::DarkGray)),
+                    Span::raw(bookmark.name.as_str()),
+                    Span::styled(
+                        format!(" {}", bookmark.path),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ])
+                .into()
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(AppUi::bookmarks_block(&self.keymap))
+            .highlight_style(Style::default().add_modifier(Modifier::ITALIC))
+            .highlight_symbol(">>");
+
+        Clear.render(area, buf);
+        StatefulWidget::render(list, area, buf, &mut self.bookmark_list_state);
+    }
+
+    /// Profiles to open a tab on, the connected ones are marked with `●`.
+    pub(crate) fn render_clusters(&mut self, area: Rect, buf: &mut Buffer) {
+        let items: Vec<ListItem> = self
+            .config
+            .profiles
+            .iter()
+            .map(|profile| {
+                let connected = self
+                    .clusters
+                    .iter()
+                    .any(|cluster| cluster.name == profile.name && cluster.zk.is_some());
+                let environment = profile.environment.map_or(Span::raw(""), |environment| {
+                    Span::styled(format!(" {environment}"), environment.color())
+                });
+                Line::from(vec![
+                    Span::raw(if connected { "● " } else { "  " }),
+                    Span::raw(profile.name.as_str()),
+                    Span::styled(
+                        format!(" {}", profile.connect),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    environment,
+                ])
+                .into()
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(AppUi::clusters_block(&self.keymap))
+            .highlight_style(Style::default().add_modifier(Modifier::ITALIC))
+            .highlight_symbol(">>");
+
+        Clear.render(area, buf);
+        StatefulWidget::render(list, area, buf, &mut self.cluster_list_state);
+    }
+
+    pub(crate) fn render_search_results(&mut self, area: Rect, buf: &mut Buffer) {
+        let tab = &mut self.tabs[self.curr_tab];
+        let items: Vec<ListItem> = tab
+            .search_results
+            .iter()
+            .map(|result| match result {
+                SearchResult::Name { path, depth } => Line::from(vec![
+                    Span::styled(format!("[{depth}] "), Style::default().fg(Color::DarkGray)),
+                    Span::raw(path.as_str()),
+                ])
+                .into(),
+                SearchResult::Data(grep_match) => Line::from(vec![
+                    Span::raw(grep_match.path.as_str()),
+                    Span::styled(
+                        format!(":{}: ", grep_match.line),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::raw(grep_match.context.as_str()),
+                ])
+                .into(),
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(AppUi::search_results_block(
+                tab.message.clone(),
+                &self.keymap,
+            ))
+            .highlight_style(Style::default().add_modifier(Modifier::ITALIC))
+            .highlight_symbol(">>");
+
+        Clear.render(area, buf);
+        StatefulWidget::render(list, area, buf, &mut tab.search_list_state);
+    }
+
     pub(crate) fn render_confirm_delete_node(&mut self, area: Rect, buf: &mut Buffer) {
         let input_rect = AppUi::confirmation_input_rect(area);
 
@@ -123,7 +579,66 @@ impl App {
             self.node_path_buf()
         ))
         .wrap(Wrap { trim: true })
-        .block(AppUi::confirm_delete_block())
+        .block(AppUi::confirm_delete_block(&self.keymap))
         .render(area, buf);
     }
+
+    pub(crate) fn render_confirm_recursive_delete_node(&mut self, area: Rect, buf: &mut Buffer) {
+        let Some(ref plan) = self.curr_tab().delete_plan else {
+            return;
+        };
+        let [preview_rect, input_rect] = AppUi::recursive_delete_popup_layout()
+            .areas(AppUi::confirm_delete_block(&self.keymap).inner(area));
+
+        let mut lines = vec![
+            Line::from(format!(
+                "{} nodes will be removed under {}",
+                plan.nodes.len(),
+                plan.root
+            )),
+            Line::from(""),
+        ];
+        lines.extend(plan.preview().map(|path| Line::from(path.as_str())));
+        if plan.nodes.len() > DELETE_PREVIEW_SIZE {
+            lines.push(Line::from(format!(
+                "... and {} more",
+                plan.nodes.len() - DELETE_PREVIEW_SIZE
+            )));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from("Type DELETE to confirm"));
+
+        Clear.render(area, buf);
+        AppUi::confirm_delete_block(&self.keymap).render(area, buf);
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: true })
+            .render(preview_rect, buf);
+        Paragraph::new(self.input_buf().as_str())
+            .block(AppUi::default_styled_block().on_red())
+            .render(input_rect, buf);
+    }
+
+    pub(crate) fn render_deleting_nodes(&mut self, area: Rect, buf: &mut Buffer) {
+        let Some(ref plan) = self.curr_tab().delete_plan else {
+            return;
+        };
+        let block = AppUi::deleting_nodes_block(&self.keymap);
+        let [gauge_rect, status_rect] =
+            AppUi::vertical_double_popup_layout().areas(block.inner(area));
+
+        Clear.render(area, buf);
+        block.render(area, buf);
+        Gauge::default()
+            .ratio(plan.ratio())
+            .label(format!("{}/{}", plan.processed, plan.nodes.len()))
+            .gauge_style(Style::default().fg(Color::Red))
+            .render(gauge_rect, buf);
+        Paragraph::new(format!(
+            "Deleted: {} | Failed: {}",
+            plan.deleted,
+            plan.failures.len()
+        ))
+        .centered()
+        .render(status_rect, buf);
+    }
 }
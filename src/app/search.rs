@@ -0,0 +1,207 @@
+use super::{recursive_delete::walk_subtree, state::TabState, App};
+use crate::{
+    errors::zk_error_message,
+    grep::{grep_subtree, GrepMatch},
+    pattern::Pattern,
+    zk_path::ZkPath,
+};
+use core::fmt;
+
+/// What the recursive search matches the pattern against.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum SearchKind {
+    #[default]
+    Names,
+    Data,
+}
+
+impl fmt::Display for SearchKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SearchKind::Names => "node names",
+            SearchKind::Data => "node data",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum SearchResult {
+    Name {
+        path: ZkPath,
+        depth: usize, // relative to the search root
+    },
+    Data(GrepMatch),
+}
+
+impl SearchResult {
+    pub fn path(&self) -> &ZkPath {
+        match self {
+            SearchResult::Name { path, .. } => path,
+            SearchResult::Data(grep_match) => &grep_match.path,
+        }
+    }
+}
+
+impl App {
+    pub(crate) fn open_filter(&mut self) {
+        let tab = self.curr_tab_mut();
+        if tab.unfiltered_data.is_none() {
+            tab.unfiltered_data = Some(tab.tab_data.clone());
+        }
+        tab.state = TabState::FilterNodes;
+    }
+
+    /// Narrows `tab_data` down to the children matching `filter_buf`.
+    pub(crate) fn apply_filter(&mut self) {
+        let tab = self.curr_tab();
+        let Some(ref unfiltered) = tab.unfiltered_data else {
+            return;
+        };
+        let filtered = match Pattern::new(tab.pattern_kind, &tab.filter_buf) {
+            Ok(pattern) => unfiltered
+                .iter()
+                .filter(|name| pattern.is_match(name))
+                .cloned()
+                .collect(),
+            Err(e) => {
+                let unfiltered = unfiltered.clone();
+                self.set_tab_message(e);
+                unfiltered
+            }
+        };
+
+        let tab = self.curr_tab_mut();
+        let selected = Some(0).filter(|_| !filtered.is_empty());
+        tab.tab_data = filtered;
+        tab.list_state.select(selected);
+        self.sync_list_selection();
+    }
+
+    pub(crate) fn submit_filter(&mut self) {
+        self.curr_tab_mut().state = TabState::Tab;
+        if self.curr_tab().filter_buf.is_empty() {
+            self.clear_filter();
+        }
+    }
+
+    /// Restores the full children list, keeping the selected node selected.
+    pub(crate) fn clear_filter(&mut self) {
+        let tab = self.curr_tab_mut();
+        tab.filter_buf.clear();
+        tab.state = TabState::Tab;
+        let Some(unfiltered) = tab.unfiltered_data.take() else {
+            return;
+        };
+        let selected = unfiltered
+            .iter()
+            .position(|name| name == tab.node_path.basename());
+        tab.tab_data = unfiltered;
+        tab.list_state.select(Some(selected.unwrap_or(0)));
+        self.sync_list_selection();
+    }
+
+    pub(crate) fn next_pattern_kind(&mut self) {
+        let kind = self.curr_tab().pattern_kind.next();
+        self.curr_tab_mut().pattern_kind = kind;
+    }
+
+    pub(crate) fn open_search(&mut self, kind: SearchKind) {
+        let root = if self.curr_tab().tree_mode {
+            self.tab_path()
+        } else {
+            self.curr_tab().listing_path.clone()
+        };
+        let tab = self.curr_tab_mut();
+        tab.search_root = root;
+        tab.search_kind = kind;
+        tab.input_buf.clear();
+        tab.state = TabState::SearchInput;
+    }
+
+    /// Walks the subtree under `search_root` and stores the nodes whose name or data matches `input_buf`.
+    pub(crate) async fn run_search(&mut self) {
+        let pattern = match Pattern::new(self.curr_tab().pattern_kind, &self.curr_tab().input_buf) {
+            Ok(pattern) => pattern,
+            Err(e) => {
+                self.set_tab_message(e);
+                return;
+            }
+        };
+        let Some(ref zk) = self.cluster().zk else {
+            "Failed to get zookeeper client".clone_into(&mut self.curr_tab_mut().message);
+            return;
+        };
+
+        let root = self.curr_tab().search_root.clone();
+        let results = match self.curr_tab().search_kind {
+            SearchKind::Names => walk_subtree(zk, &root).await.map(|nodes| {
+                nodes
+                    .into_iter()
+                    .filter(|path| *path != root && pattern.is_match(path.basename()))
+                    .map(|path| SearchResult::Name {
+                        depth: path.depth() - root.depth(),
+                        path,
+                    })
+                    .collect::<Vec<_>>()
+            }),
+            SearchKind::Data => grep_subtree(zk, &root, &pattern)
+                .await
+                .map(|matches| matches.into_iter().map(SearchResult::Data).collect()),
+        };
+        let results = match results {
+            Ok(results) => results,
+            Err((path, e)) => {
+                self.set_tab_message(zk_error_message("search", &path, &e));
+                return;
+            }
+        };
+
+        let tab = self.curr_tab_mut();
+        let matched = match tab.search_kind {
+            SearchKind::Names => "nodes",
+            SearchKind::Data => "lines",
+        };
+        tab.message = format!(
+            "{} {matched} under {root} match {}",
+            results.len(),
+            tab.input_buf
+        );
+        tab.search_list_state
+            .select(Some(0).filter(|_| !results.is_empty()));
+        tab.search_results = results;
+        tab.state = TabState::SearchResults;
+    }
+
+    pub(crate) fn next_search_result(&mut self) {
+        let tab = self.curr_tab_mut();
+        let i = match tab.search_list_state.selected() {
+            Some(i) if i + 1 < tab.search_results.len() => i + 1,
+            _ => 0,
+        };
+        tab.search_list_state.select(Some(i));
+    }
+
+    pub(crate) fn previous_search_result(&mut self) {
+        let tab = self.curr_tab_mut();
+        let i = match tab.search_list_state.selected() {
+            Some(0) | None => tab.search_results.len().saturating_sub(1),
+            Some(i) => i - 1,
+        };
+        tab.search_list_state.select(Some(i));
+    }
+
+    pub(crate) async fn open_search_result(&mut self) {
+        let tab = self.curr_tab_mut();
+        let Some(result) = tab
+            .search_list_state
+            .selected()
+            .and_then(|i| tab.search_results.get(i))
+        else {
+            return;
+        };
+        let path = result.path().clone();
+        tab.state = TabState::Tab;
+        self.open_path(&path).await;
+    }
+}
@@ -0,0 +1,92 @@
+use super::App;
+use ratatui::{
+    style::{palette::tailwind, Color, Stylize},
+    text::Line,
+};
+use std::time::{Duration, Instant};
+use zookeeper_async::KeeperState;
+
+pub const RECONNECT_INTERVAL: Duration = Duration::from_secs(2);
+
+impl App {
+    pub(crate) async fn handle_session_event(&mut self, cluster: usize, state: KeeperState) {
+        if self.clusters[cluster].session_state == Some(state) {
+            return;
+        }
+        self.clusters[cluster].session_state = Some(state);
+        match state {
+            KeeperState::Expired => {
+                self.append_cluster_message(cluster, "Session expired, reconnecting\n");
+                self.reconnect(cluster).await;
+            }
+            KeeperState::Disconnected => {
+                self.append_cluster_message(cluster, "Connection lost, data may be stale\n")
+            }
+            KeeperState::AuthFailed => self.append_cluster_message(
+                cluster,
+                "Authentication failed, check the connection digest credentials\n",
+            ),
+            _ => {}
+        }
+    }
+
+    /// Reports a session change in every tab connected to `cluster`.
+    fn append_cluster_message(&mut self, cluster: usize, msg: &str) {
+        for tab in self.tabs.iter_mut().filter(|tab| tab.cluster == cluster) {
+            tab.message.push_str(msg);
+        }
+    }
+
+    /// Replaces the expired session with a new one and re-opens the cluster tabs at their paths.
+    /// The next attempt is scheduled first, so a cancelled reconnect is still retried.
+    pub(crate) async fn reconnect(&mut self, cluster: usize) {
+        self.clusters[cluster].next_reconnect = Some(Instant::now() + RECONNECT_INTERVAL);
+        match self.clusters[cluster].connect().await {
+            Ok(()) => {
+                self.reopen_tabs(cluster).await;
+                self.append_cluster_message(cluster, "Session re-established\n");
+            }
+            Err(e) => {
+                self.clusters[cluster].next_reconnect = Some(Instant::now() + RECONNECT_INTERVAL);
+                self.append_cluster_message(
+                    cluster,
+                    &format!("Reconnect failed : {e}, retrying in {RECONNECT_INTERVAL:?}\n"),
+                );
+            }
+        }
+    }
+
+    async fn reopen_tabs(&mut self, cluster: usize) {
+        let curr_tab = self.curr_tab;
+        for index in 0..self.tabs.len() {
+            self.curr_tab = index;
+            if self.curr_tab().cluster != cluster || self.curr_tab().tab_data.is_empty() {
+                continue;
+            }
+
+            self.reload_tab().await;
+        }
+        self.curr_tab = curr_tab;
+    }
+
+    pub fn session_title(&self) -> Line<'static> {
+        let (title, color) = match self.cluster().session_state {
+            Some(KeeperState::SyncConnected) | Some(KeeperState::SaslAuthenticated) => {
+                ("Connected", tailwind::GREEN.c700)
+            }
+            Some(KeeperState::ConnectedReadOnly) => {
+                ("Connected to a read-only server", tailwind::AMBER.c700)
+            }
+            Some(KeeperState::Disconnected) => {
+                ("Disconnected, data may be stale", tailwind::RED.c700)
+            }
+            Some(KeeperState::Expired) => ("Session expired, data is stale", tailwind::RED.c700),
+            Some(KeeperState::AuthFailed) => ("Authentication failed", tailwind::RED.c700),
+            None => ("Not connected", Color::DarkGray),
+        };
+        format!(" {title} ")
+            .fg(tailwind::SLATE.c200)
+            .bg(color)
+            .into()
+    }
+}
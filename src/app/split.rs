@@ -0,0 +1,111 @@
+use super::{action::Action, keymap::KeyContext, state::TabState, App};
+use crate::{errors::zk_error_message, import::ImportPlan, snapshot::Snapshot};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SplitOrientation {
+    Vertical,   // panes side by side
+    Horizontal, // panes one above the other
+}
+
+/// Second tab shown next to the current one, the current tab is always the focused pane.
+#[derive(Debug, Clone, Copy)]
+pub struct Split {
+    pub orientation: SplitOrientation,
+    pub other: usize,      // index of the tab in the unfocused pane
+    pub focus_first: bool, // the focused pane is the left or top one
+}
+
+impl App {
+    /// Cycles through the vertical split, the horizontal split and a single pane.
+    pub(crate) async fn toggle_split(&mut self) {
+        self.split = match self.split {
+            None => {
+                if self.tabs.len() == 1 {
+                    self.new_tab().await;
+                }
+                let other = (self.curr_tab + 1) % self.tabs.len();
+                Some(Split {
+                    orientation: SplitOrientation::Vertical,
+                    other,
+                    focus_first: self.curr_tab < other,
+                })
+            }
+            Some(split) if split.orientation == SplitOrientation::Vertical => Some(Split {
+                orientation: SplitOrientation::Horizontal,
+                ..split
+            }),
+            Some(_) => None,
+        };
+    }
+
+    pub(crate) async fn focus_other_pane(&mut self) {
+        if let Some(split) = self.split {
+            self.switch_tab(split.other).await;
+        }
+    }
+
+    /// Reloads the unfocused pane when a watch reported a change in it.
+    pub(crate) async fn refresh_other_pane(&mut self) {
+        let Some(split) = self.split else {
+            return;
+        };
+        let curr_tab = self.curr_tab;
+        self.curr_tab = split.other;
+        self.reload_stale_tab().await;
+        self.curr_tab = curr_tab;
+    }
+
+    /// Plans copying the selected node and its subtree under the node listed in the other pane.
+    /// The plan is previewed in the other pane, which may be connected to another cluster.
+    pub(crate) async fn copy_to_other_pane(&mut self) {
+        let Some(split) = self.split else {
+            let hint = self.keymap.hints(
+                KeyContext::Nodes,
+                &[(Action::ToggleSplit, "split the screen")],
+            );
+            self.set_tab_message(format!("Press {hint} and copy nodes between panes"));
+            return;
+        };
+        let source = self.tab_path();
+        if source.is_root() {
+            self.set_tab_message("The root node can't be copied".to_owned());
+            return;
+        }
+        let Some(ref zk) = self.cluster().zk else {
+            "Failed to get zookeeper client".clone_into(&mut self.curr_tab_mut().message);
+            return;
+        };
+        let snapshot = match Snapshot::take(zk, &source).await {
+            Ok(snapshot) => snapshot,
+            Err((path, e)) => {
+                self.set_tab_message(zk_error_message("copy", &path, &e));
+                return;
+            }
+        };
+
+        self.switch_tab(split.other).await;
+        let parent = if self.curr_tab().tree_mode {
+            self.tab_path()
+        } else {
+            self.curr_tab().listing_path.clone()
+        };
+        let target = match parent.join(source.basename()) {
+            Ok(target) => target,
+            Err(e) => {
+                self.set_tab_message(e.to_string());
+                return;
+            }
+        };
+        let Some(ref zk) = self.cluster().zk else {
+            "Failed to get zookeeper client".clone_into(&mut self.curr_tab_mut().message);
+            return;
+        };
+        let strategy = self.curr_tab().import_strategy;
+        let chroot = self.cluster().chroot();
+        let plan = ImportPlan::build(zk, chroot.as_deref(), &snapshot, &target, strategy).await;
+        let tab = self.curr_tab_mut();
+        tab.message = plan.summary();
+        tab.import_plan = Some(plan);
+        tab.state = TabState::CopyPreview;
+    }
+}
@@ -1,19 +1,47 @@
-#[derive(Debug, Default, PartialEq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub enum AppState {
     #[default]
     EstablishingConnection,
     EditingConnection,
+    EditingAuth,
+    EditingProfileName,
+    ConfirmDeleteProfile,
+    ConnectionFailed,
     Tab,
 }
 
-#[derive(Debug, Default, PartialEq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub enum TabState {
     #[default]
     Tab,
     ReadNodeData,
     EditCreateNodePath,
     EditCreateNodeData,
+    EditCreateNodeMode,
+    EditCreateNodeTtl,
+    EditCreateNodeAcl,
+    EditCreateNodeCredentials,
     EditNodeData,
     DeleteNode,
     ConfirmDelete,
+    DeletingNodes,
+    EditAcl,
+    EditAclEntry,
+    ExportSubtree,
+    ImportSnapshot,
+    ImportPreview,
+    CopyPreview, // import plan of a node copied from the other pane
+    EditDiffLeft,
+    EditDiffRight,
+    EditDiffConnection,
+    EditDiffAuth,
+    DiffView,
+    FilterNodes,
+    SearchInput,
+    SearchResults,
+    GoToPath,
+    EditBookmarkName,
+    Bookmarks,
+    RenameTab,
+    Clusters,
 }
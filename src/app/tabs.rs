@@ -0,0 +1,123 @@
+use super::{state::TabState, App};
+use crate::tab::Tab;
+use std::mem;
+
+impl App {
+    /// Switches to the tab at `index`, reloading it only when a watch reported a change.
+    /// In the split mode the focused pane shows the tab, or the focus moves to the other pane.
+    pub(crate) async fn switch_tab(&mut self, index: usize) {
+        if index >= self.tabs.len() || index == self.curr_tab {
+            return;
+        }
+        if let Some(split) = self.split.as_mut().filter(|split| split.other == index) {
+            split.other = self.curr_tab;
+            split.focus_first = !split.focus_first;
+        }
+        self.curr_tab = index;
+        self.reload_stale_tab().await;
+    }
+
+    pub(crate) async fn reload_stale_tab(&mut self) {
+        if !mem::take(&mut self.curr_tab_mut().stale) {
+            return;
+        }
+        self.reload_tab().await;
+    }
+
+    pub(crate) async fn next_tab(&mut self) {
+        self.switch_tab((self.curr_tab + 1) % self.tabs.len()).await;
+    }
+
+    pub(crate) async fn previous_tab(&mut self) {
+        let len = self.tabs.len();
+        self.switch_tab((self.curr_tab + len - 1) % len).await;
+    }
+
+    /// Opens a tab at the current node, right after the current tab.
+    pub(crate) async fn new_tab(&mut self) {
+        let tab = Tab {
+            cluster: self.curr_tab().cluster,
+            tree_mode: self.curr_tab().tree_mode,
+            toggle_stats_auto_load: self.curr_tab().toggle_stats_auto_load,
+            ..Default::default()
+        };
+        self.insert_tab(tab).await;
+    }
+
+    /// Opens a copy of the current tab with its name and history.
+    pub(crate) async fn duplicate_tab(&mut self) {
+        let curr = self.curr_tab();
+        let tab = Tab {
+            name: curr.name.clone(),
+            cluster: curr.cluster,
+            history: curr.history.clone(),
+            tree_mode: curr.tree_mode,
+            toggle_stats_auto_load: curr.toggle_stats_auto_load,
+            ..Default::default()
+        };
+        self.insert_tab(tab).await;
+    }
+
+    async fn insert_tab(&mut self, tab: Tab) {
+        let path = self.tab_path();
+        self.add_tab(tab);
+        self.show_path(&path).await;
+    }
+
+    /// Inserts `tab` right after the current tab and switches to it.
+    pub(crate) fn add_tab(&mut self, tab: Tab) {
+        let index = self.curr_tab + 1;
+        self.tabs.insert(index, tab);
+        if let Some(split) = self.split.as_mut().filter(|split| split.other >= index) {
+            split.other += 1;
+        }
+        self.curr_tab = index;
+    }
+
+    pub(crate) async fn close_tab(&mut self) {
+        if self.tabs.len() == 1 {
+            self.set_tab_message("The last tab can't be closed".to_owned());
+            return;
+        }
+        let closed = self.curr_tab;
+        let tab = self.tabs.remove(closed);
+        // the other pane takes the whole screen
+        self.curr_tab = match self.split.take() {
+            Some(split) if split.other > closed => split.other - 1,
+            Some(split) => split.other,
+            None => closed.min(self.tabs.len() - 1),
+        };
+        self.release_cluster(tab.cluster).await;
+        self.reload_stale_tab().await;
+    }
+
+    pub(crate) fn open_rename_tab(&mut self) {
+        let tab = self.curr_tab_mut();
+        tab.input_buf = tab.name.clone().unwrap_or_default();
+        tab.state = TabState::RenameTab;
+    }
+
+    /// Names the tab after `input_buf`, an empty name brings back the path title.
+    pub(crate) fn rename_tab(&mut self) {
+        let tab = self.curr_tab_mut();
+        let name = mem::take(&mut tab.input_buf).trim().to_owned();
+        tab.name = Some(name).filter(|name| !name.is_empty());
+        tab.state = TabState::Tab;
+    }
+
+    /// Marks the background tabs of `cluster` showing `path` so they are reloaded when switched to.
+    pub(crate) fn mark_stale_tabs(&mut self, cluster: usize, path: &str) {
+        let curr_tab = self.curr_tab;
+        for (index, tab) in self.tabs.iter_mut().enumerate() {
+            if index == curr_tab || tab.cluster != cluster {
+                continue;
+            }
+            if tab.listing_path.as_str() == path
+                || tab.node_path.as_str() == path
+                || (tab.tree_mode && tab.tree.position(path).is_some())
+            {
+                tab.stale = true;
+            }
+        }
+    }
+}
@@ -0,0 +1,277 @@
+use super::App;
+use crate::zk_path::ZkPath;
+use ratatui::widgets::ListState;
+
+#[derive(Debug, Clone)]
+pub struct TreeNode {
+    pub path: ZkPath,
+    pub name: String,
+    pub depth: usize,
+    pub expanded: bool,
+    pub loaded: bool, // children were fetched at least once
+    pub has_children: bool,
+    pub guides: String, // indentation guides drawn before the name
+}
+
+impl TreeNode {
+    fn new(path: ZkPath, name: String, depth: usize) -> Self {
+        Self {
+            path,
+            name,
+            depth,
+            expanded: false,
+            loaded: false,
+            has_children: true,
+            guides: String::new(),
+        }
+    }
+
+    pub fn marker(&self) -> &'static str {
+        match (self.expanded, self.loaded && !self.has_children) {
+            (_, true) => "· ",
+            (true, _) => "▾ ",
+            (false, _) => "▸ ",
+        }
+    }
+}
+
+/// Visible rows of the tree mode, every expanded node is followed by its subtree.
+#[derive(Debug)]
+pub struct TreeView {
+    pub nodes: Vec<TreeNode>,
+    pub list_state: ListState,
+}
+
+impl Default for TreeView {
+    fn default() -> Self {
+        Self {
+            nodes: vec![TreeNode::new(ZkPath::root(), ZkPath::root().to_string(), 0)],
+            list_state: ListState::default().with_selected(Some(0)),
+        }
+    }
+}
+
+impl TreeView {
+    pub fn selected(&self) -> Option<&TreeNode> {
+        self.list_state.selected().and_then(|i| self.nodes.get(i))
+    }
+
+    pub fn position(&self, path: &str) -> Option<usize> {
+        self.nodes
+            .iter()
+            .position(|node| node.path.as_str() == path)
+    }
+
+    /// Index right after the last visible descendant of the node at `index`.
+    fn subtree_end(&self, index: usize) -> usize {
+        let depth = self.nodes[index].depth;
+        self.nodes[index + 1..]
+            .iter()
+            .position(|node| node.depth <= depth)
+            .map_or(self.nodes.len(), |offset| index + 1 + offset)
+    }
+
+    fn collapse(&mut self, index: usize) {
+        let end = self.subtree_end(index);
+        self.nodes.drain(index + 1..end);
+        self.nodes[index].expanded = false;
+        self.refresh_guides();
+    }
+
+    fn expand(&mut self, index: usize, mut children: Vec<String>) {
+        self.collapse(index);
+        children.sort_unstable();
+        let parent = &self.nodes[index];
+        let rows: Vec<TreeNode> = children
+            .into_iter()
+            .filter_map(|name| {
+                let path = parent.path.join(&name).ok()?;
+                Some(TreeNode::new(path, name, parent.depth + 1))
+            })
+            .collect();
+
+        let parent = &mut self.nodes[index];
+        parent.loaded = true;
+        parent.has_children = !rows.is_empty();
+        parent.expanded = parent.has_children;
+        self.nodes.splice(index + 1..index + 1, rows);
+        self.refresh_guides();
+    }
+
+    /// Rebuilds the `├─`/`└─` guides, scanning backwards to know which nodes have later siblings.
+    fn refresh_guides(&mut self) {
+        let mut has_next_sibling: Vec<bool> = Vec::new();
+        for node in self.nodes.iter_mut().rev() {
+            let depth = node.depth;
+            has_next_sibling.resize(depth + 1, false);
+            node.guides = if depth == 0 {
+                String::new()
+            } else {
+                let mut guides: String = has_next_sibling[1..depth]
+                    .iter()
+                    .map(|next| if *next { "│  " } else { "   " })
+                    .collect();
+                guides.push_str(if has_next_sibling[depth] {
+                    "├─ "
+                } else {
+                    "└─ "
+                });
+                guides
+            };
+            has_next_sibling[depth] = true;
+        }
+    }
+}
+
+impl App {
+    /// Switches between the flat list and the tree, opening the tree at the current path.
+    pub(crate) async fn toggle_tree_mode(&mut self) {
+        let tree_mode = !self.curr_tab().tree_mode;
+        self.curr_tab_mut().tree_mode = tree_mode;
+        if tree_mode {
+            self.open_tree().await;
+        } else {
+            self.open_flat_list().await;
+        }
+    }
+
+    /// Rebuilds the tree with the nodes on the way to the current path expanded.
+    pub(crate) async fn open_tree(&mut self) {
+        let path = self.tab_path();
+        self.curr_tab_mut().tree = TreeView::default();
+        self.expand_tree_node(0).await;
+        for ancestor in path.ancestors().skip(1) {
+            let Some(index) = self.curr_tab().tree.position(&ancestor) else {
+                break;
+            };
+            self.curr_tab_mut().tree.list_state.select(Some(index));
+            if ancestor != path {
+                self.expand_tree_node(index).await;
+            }
+        }
+        self.sync_tree_selection();
+    }
+
+    /// Lists the parent of the node selected in the tree and selects the node in the flat list.
+    pub(crate) async fn open_flat_list(&mut self) {
+        let selected = self.tab_path();
+        let parent = selected.parent().unwrap_or_default();
+        self.store_curr_tab_children_by_path(&parent).await;
+
+        let index = self
+            .curr_tab()
+            .tab_data
+            .iter()
+            .position(|name| name == selected.basename());
+        self.curr_tab_mut()
+            .list_state
+            .select(Some(index.unwrap_or(0)));
+        self.sync_list_selection();
+    }
+
+    async fn expand_tree_node(&mut self, index: usize) {
+        let path = self.curr_tab().tree.nodes[index].path.clone();
+        if let Some(children) = self.get_children(&path).await {
+            self.curr_tab_mut().tree.expand(index, children);
+        }
+    }
+
+    pub(crate) async fn toggle_tree_node(&mut self) {
+        let Some(index) = self.curr_tab().tree.list_state.selected() else {
+            return;
+        };
+        if self.curr_tab().tree.nodes[index].expanded {
+            self.curr_tab_mut().tree.collapse(index);
+        } else {
+            self.expand_tree_node(index).await;
+            if !self.curr_tab().tree.nodes[index].has_children {
+                self.set_tab_message("Node does not have children nodes".to_owned());
+            }
+        }
+    }
+
+    /// Collapses the selected node, or moves to its parent when it is already collapsed.
+    pub(crate) fn collapse_tree_node(&mut self) {
+        let tree = &mut self.curr_tab_mut().tree;
+        let Some(index) = tree.list_state.selected() else {
+            return;
+        };
+        if tree.nodes[index].expanded {
+            tree.collapse(index);
+            return;
+        }
+        let depth = tree.nodes[index].depth;
+        if let Some(parent) = tree.nodes[..index].iter().rposition(|n| n.depth < depth) {
+            tree.list_state.select(Some(parent));
+        }
+        self.sync_tree_selection();
+    }
+
+    pub(crate) fn tree_next(&mut self) {
+        let tree = &mut self.curr_tab_mut().tree;
+        let i = match tree.list_state.selected() {
+            Some(i) if i + 1 < tree.nodes.len() => i + 1,
+            _ => 0,
+        };
+        tree.list_state.select(Some(i));
+        self.sync_tree_selection();
+    }
+
+    pub(crate) fn tree_previous(&mut self) {
+        let tree = &mut self.curr_tab_mut().tree;
+        let i = match tree.list_state.selected() {
+            Some(0) | None => tree.nodes.len().saturating_sub(1),
+            Some(i) => i - 1,
+        };
+        tree.list_state.select(Some(i));
+        self.sync_tree_selection();
+    }
+
+    /// Points the tab path at the selected tree node so node operations work in the tree mode.
+    fn sync_tree_selection(&mut self) {
+        if let Some(path) = self
+            .curr_tab()
+            .tree
+            .selected()
+            .map(|node| node.path.clone())
+        {
+            self.curr_tab_mut().node_path = path;
+        }
+    }
+
+    /// Reloads the children of an expanded tree node after a watch event.
+    pub(crate) async fn refresh_tree_node(&mut self, path: &str) -> bool {
+        let Some(index) = self.curr_tab().tree.position(path) else {
+            return false;
+        };
+        if !self.curr_tab().tree.nodes[index].expanded {
+            return false;
+        }
+        let selected = self
+            .curr_tab()
+            .tree
+            .selected()
+            .map(|node| node.path.clone());
+        let tree = &self.curr_tab().tree;
+        let expanded: Vec<ZkPath> = tree.nodes[index + 1..tree.subtree_end(index)]
+            .iter()
+            .filter(|node| node.expanded)
+            .map(|node| node.path.clone())
+            .collect();
+
+        self.expand_tree_node(index).await;
+        for path in expanded {
+            if let Some(index) = self.curr_tab().tree.position(&path) {
+                self.expand_tree_node(index).await;
+            }
+        }
+
+        let tree = &mut self.curr_tab_mut().tree;
+        let position = selected
+            .and_then(|path| tree.position(&path))
+            .unwrap_or(index);
+        tree.list_state.select(Some(position));
+        self.sync_tree_selection();
+        true
+    }
+}
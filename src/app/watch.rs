@@ -0,0 +1,67 @@
+use super::{state::TabState, App};
+use crate::{node_data::NodeData, zk_path::ZkPath};
+use zookeeper_async::{WatchedEvent, WatchedEventType};
+
+impl App {
+    /// Remembers a change made by this client so its watch event is not reported as foreign.
+    pub(crate) fn record_own_change(&mut self, path: &ZkPath) {
+        let cluster = self.cluster_mut();
+        cluster.own_changes.record(path, cluster.session_timeout);
+    }
+
+    pub(crate) async fn handle_watched_event(&mut self, cluster: usize, event: WatchedEvent) {
+        let Some(path) = event.path else {
+            return;
+        };
+        let is_own_change = self.clusters[cluster].own_changes.take(&path);
+        self.mark_stale_tabs(cluster, &path);
+        self.refresh_other_pane().await;
+        if self.curr_tab().cluster != cluster {
+            return;
+        }
+
+        let refreshed = match event.event_type {
+            WatchedEventType::NodeChildrenChanged if self.curr_tab().tree_mode => {
+                self.refresh_tree_node(&path).await
+            }
+            WatchedEventType::NodeChildrenChanged
+                if path == self.curr_tab().listing_path.as_str() =>
+            {
+                self.reload_listing().await;
+                true
+            }
+            WatchedEventType::NodeDataChanged
+            | WatchedEventType::NodeCreated
+            | WatchedEventType::NodeDeleted
+                if path == self.curr_tab().node_path.as_str() =>
+            {
+                self.refresh_node().await;
+                true
+            }
+            _ => false,
+        };
+
+        if refreshed && !is_own_change {
+            self.append_tab_message(format!("Node {path} was changed by another client\n"));
+        }
+    }
+
+    async fn refresh_node(&mut self) {
+        self.auto_load_stat().await;
+        if !matches!(
+            self.curr_tab().state,
+            TabState::ReadNodeData | TabState::EditNodeData
+        ) {
+            return;
+        }
+
+        let format = self.curr_tab().node_data.clone();
+        self.store_node_data().await;
+        let data = self.curr_tab().node_data.clone();
+        self.curr_tab_mut().node_data = match format {
+            NodeData::Raw(_) => data,
+            NodeData::String(_) => data.convert_to_string(),
+            NodeData::Json(_) => data.convert_to_json(),
+        };
+    }
+}
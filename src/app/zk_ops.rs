@@ -1,135 +1,258 @@
-use super::App;
+use super::{cluster::Cluster, state::AppState, App};
 use crate::{
-    errors::{AppError, AppResult},
+    errors::{zk_error_message, AppError, AppResult},
     node_data::NodeData,
-    zk::LoggingWatcher,
+    node_ops,
+    zk_path::ZkPath,
 };
 use std::{mem, time::Duration};
-use zookeeper_async::{Acl, ZooKeeper};
+use zookeeper_async::{Watcher, ZooKeeper};
+
+pub const DEFAULT_SESSION_TIMEOUT: Duration = Duration::from_secs(1);
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Empty credentials mean no authentication, others need a non-empty user and password.
+pub(crate) fn check_credentials(auth: &str) -> AppResult<()> {
+    match auth.split_once(':') {
+        _ if auth.is_empty() => Ok(()),
+        Some((user, password)) if !user.is_empty() && !password.is_empty() => Ok(()),
+        _ => Err(AppError::InvalidCredentials),
+    }
+}
 
 impl App {
-    pub(crate) async fn connect_default(connection_str: &str) -> AppResult<ZooKeeper> {
-        //TODO: Make timeout configurable
-        //TODO: Design Failure window
-        tokio::select! {
-            res = ZooKeeper::connect(connection_str, Duration::from_secs(1), LoggingWatcher) => res.map_err(Into::into),
-            _ = tokio::time::sleep(Duration::from_secs(3)) => {
-                Err(AppError::ConnectionTimeoutError)
+    /// Connects with the current inputs, switching to the failure popup instead of bailing out.
+    pub(crate) async fn connect(&mut self) {
+        if !self.parse_connection() {
+            return;
+        }
+        self.connection_attempts += 1;
+        let profile = self
+            .selected_profile()
+            .filter(|profile| profile.connect == self.connection_input);
+        let mut cluster = Cluster {
+            name: match (profile, &self.connection) {
+                (Some(profile), _) => profile.name.clone(),
+                (None, Some(connection)) => connection.hosts[0].to_string(),
+                (None, None) => self.connection_str(),
+            },
+            environment: profile.and_then(|profile| profile.environment),
+            connection: self.connection_str(),
+            auth: self.auth_input.clone(),
+            session_timeout: self.session_timeout,
+            connect_timeout: self.connect_timeout,
+            mode: self.mode,
+            start_path: self.start_path.clone(),
+            watches: self.watch_channel(self.curr_tab().cluster),
+            ..Default::default()
+        };
+        match cluster.connect().await {
+            Ok(()) => {
+                let index = self.curr_tab().cluster;
+                self.clusters[index] = cluster;
+                self.connection_failure = None;
+                self.connection_attempts = 0;
+                self.open_start_path().await;
+                self.state = AppState::Tab;
             }
+            Err(e) => {
+                self.connection_failure = Some(e.to_string());
+                self.state = AppState::ConnectionFailed;
+            }
+        }
+    }
+
+    pub(crate) async fn connect_default(
+        connection_str: &str,
+        auth: &str,
+        session_timeout: Duration,
+        connect_timeout: Duration,
+        watcher: impl Watcher + 'static,
+    ) -> AppResult<ZooKeeper> {
+        check_credentials(auth)?;
+        let zk = tokio::select! {
+            res = ZooKeeper::connect(connection_str, session_timeout, watcher) => res?,
+            _ = tokio::time::sleep(connect_timeout) => {
+                return Err(AppError::ConnectionTimeoutError(connect_timeout));
+            }
+        };
+        if !auth.is_empty() {
+            zk.add_auth("digest", auth.as_bytes().to_vec()).await?;
         }
+        Ok(zk)
     }
 
     pub(crate) async fn store_node_stat(&mut self) {
-        let full_path = self.tab_full_resource_path();
-        let _ = self
-            .zk
-            .as_ref()
-            .unwrap()
-            .exists(&full_path, false)
-            .await
-            .map(|stat| {
-                self.curr_tab_mut().current_node_stat = stat;
-            });
+        let Some(ref zk) = self.cluster().zk else {
+            "Failed to get zookeeper client".clone_into(&mut self.curr_tab_mut().message);
+            return;
+        };
+        let full_path = self.tab_path();
+        let res = zk
+            .exists_w(&full_path, self.cluster().watches.watcher())
+            .await;
+        match res {
+            Ok(stat) => self.curr_tab_mut().current_node_stat = stat,
+            Err(e) => self.set_tab_message(zk_error_message("read stat of", &full_path, &e)),
+        }
+        self.store_node_acl().await;
     }
 
-    pub(crate) async fn store_children(&mut self, children: Vec<String>) {
+    fn store_children(&mut self, path: &ZkPath, children: Vec<String>) {
         {
             self.clear_tab_message();
-            self.append_tab_message(format!("Full path : {}\n", self.tab_full_resource_path()));
+            self.append_tab_message(format!("Full path : {path}\n"));
         }
 
-        self.curr_tab_mut().tab_data = children;
+        let tab = self.curr_tab_mut();
+        tab.listing_path = path.clone();
+        tab.unfiltered_data = None;
+        tab.filter_buf.clear();
+        tab.tab_data = children;
+        tab.list_state.select(Some(0));
+    }
+
+    /// Lists the children of `path`, leaving the listing as is when there are none.
+    pub(crate) async fn store_curr_tab_children_by_path(&mut self, path: &ZkPath) -> bool {
+        let Some(children) = self.get_children(path).await else {
+            return false;
+        };
+        if children.is_empty() {
+            self.set_tab_message("Node does not have children nodes".to_owned());
+            return false;
+        }
+        self.store_children(path, children);
+        true
     }
 
-    pub(crate) async fn store_curr_tab_children_by_path(&mut self, path: &str) {
-        let children = self.get_children(path).await;
-        if let Some(children) = children {
-            if !children.is_empty() {
-                self.store_children(children).await;
-                self.curr_tab_mut().list_state.select(Some(0));
-            } else {
-                self.set_tab_message("Node does not have children nodes".to_owned());
+    /// Parses `node_path_buf`, reporting an invalid path in the tab message.
+    pub(crate) fn parse_node_path_buf(&mut self) -> Option<ZkPath> {
+        match ZkPath::parse(self.curr_tab().node_path_buf.trim()) {
+            Ok(path) => Some(path),
+            Err(e) => {
+                self.set_tab_message(e.to_string());
+                None
             }
         }
     }
 
     pub(crate) async fn store_node_data(&mut self) {
-        let Some(ref zk) = self.zk else {
+        let Some(ref zk) = self.cluster().zk else {
             return;
         };
 
-        let _ = zk
-            .get_data(&self.tab_full_resource_path(), false)
-            .await
-            .map(|(data, _)| {
-                self.curr_tab_mut().node_data = NodeData::Raw(data);
-            });
+        let path = self.tab_path();
+        match node_ops::get_data(zk, &path, self.cluster().watches.watcher()).await {
+            Ok((data, _)) => self.curr_tab_mut().node_data = NodeData::Raw(data),
+            Err(e) => self.set_tab_message(e.to_string()),
+        }
     }
 
     pub(crate) async fn create_node(&mut self) {
-        let Some(ref zk) = self.zk else {
+        if !self.ensure_can_write() {
+            return;
+        }
+        let Some(path) = self.parse_node_path_buf() else {
+            return;
+        };
+        let Some(ref zk) = self.cluster().zk else {
             "Failed to get zookeeper client".clone_into(&mut self.curr_tab_mut().message);
             return;
         };
 
-        let res = zk
-            .create(
-                &self.curr_tab().node_path_buf,
-                self.curr_tab().node_data_buf.clone().into_bytes(), //TODO: Avoid clonning
-                Acl::open_unsafe().clone(),
-                zookeeper_async::CreateMode::Persistent,
-            )
-            .await;
+        let tab = self.curr_tab();
+        let mode = tab.create_mode;
+        let data = tab.node_data_buf.clone().into_bytes(); //TODO: Avoid clonning
+        let ttl = tab.ttl_buf.trim().parse::<u64>().ok();
+        let res = node_ops::create(
+            zk,
+            &path,
+            data,
+            mode,
+            ttl,
+            tab.acl_preset,
+            &tab.acl_credentials_buf,
+        )
+        .await;
         match res {
             Ok(created_path) => {
-                self.curr_tab_mut().message = format!("Node {created_path} created successfully")
+                // sequential nodes get a suffix, but the watched parent is the same
+                self.record_own_change(&path);
+                self.curr_tab_mut().message =
+                    format!("{mode} node {created_path} created successfully")
             }
-            Err(e) => self.curr_tab_mut().message = format!("Node creation failed : {e}"),
+            Err(e) => self.curr_tab_mut().message = e.to_string(),
         }
     }
 
     pub(crate) async fn set_data(&mut self) {
+        if !self.ensure_can_write() {
+            return;
+        }
         let data = mem::take(&mut self.curr_tab_mut().node_data_buf).into_bytes();
 
-        let Some(ref zk) = self.zk else {
+        let Some(ref zk) = self.cluster().zk else {
             "Failed to get zookeeper client".clone_into(&mut self.curr_tab_mut().message);
             return;
         };
 
-        let res = zk
-            .set_data(&self.tab_full_resource_path(), data, None)
-            .await;
+        let path = self.tab_path();
+        let res = node_ops::set_data(zk, &path, data).await;
         match res {
             Ok(_) => {
-                self.curr_tab_mut().message = format!(
-                    "Node {} data successfully updated",
-                    self.tab_full_resource_path()
-                )
+                self.record_own_change(&path);
+                self.curr_tab_mut().message = format!("Node {path} data successfully updated")
             }
-            Err(e) => self.curr_tab_mut().message = format!("Node data update failed : {e}"),
+            Err(e) => self.curr_tab_mut().message = e.to_string(),
         }
     }
 
     pub(crate) async fn delete_node(&mut self) {
-        let Some(ref zk) = self.zk else {
+        if !self.ensure_can_delete() {
+            return;
+        }
+        let Some(path) = self.parse_node_path_buf() else {
+            return;
+        };
+        let Some(ref zk) = self.cluster().zk else {
             "Failed to get zookeeper client".clone_into(&mut self.curr_tab_mut().message);
             return;
         };
-        let res = zk.delete(&self.tab_full_resource_path(), None).await;
+        let res = node_ops::delete(zk, &path).await;
         match res {
             Ok(_) => {
-                self.curr_tab_mut().message = format!(
-                    "Node {} successfully deleted",
-                    self.tab_full_resource_path()
-                )
+                self.record_own_change(&path);
+                self.curr_tab_mut().message = format!("Node {path} successfully deleted")
             }
-            Err(e) => self.curr_tab_mut().message = format!("Delete node failed : {e}"),
+            Err(e) => self.curr_tab_mut().message = e.to_string(),
         }
     }
-    pub(crate) async fn get_children(&self, path: &str) -> Option<Vec<String>> {
-        let Some(ref zk) = self.zk else {
-            return None;
-        };
-        zk.get_children(path, false).await.ok()
+    pub(crate) async fn get_children(&mut self, path: &ZkPath) -> Option<Vec<String>> {
+        let zk = self.cluster().zk.as_ref()?;
+        match zk
+            .get_children_w(path, self.cluster().watches.watcher())
+            .await
+        {
+            Ok(children) => Some(children),
+            Err(e) => {
+                self.set_tab_message(zk_error_message("list children of", path, &e));
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_credentials() {
+        assert!(check_credentials("").is_ok());
+        assert!(check_credentials("user:password").is_ok());
+        assert!(check_credentials("user:pass:word").is_ok());
+        assert!(check_credentials("user").is_err());
+        assert!(check_credentials(":password").is_err());
+        assert!(check_credentials("user:").is_err());
     }
 }
@@ -1,24 +1,173 @@
-use crate::app::connection::Connection;
-use clap::Parser;
+use crate::{
+    acl::AclPreset, app::mode::AppMode, create_mode::NodeCreateMode, import::ImportStrategy,
+    pattern::PatternKind, zk_path::ZkPath,
+};
+use clap::{error::ErrorKind, CommandFactory, Parser, Subcommand, ValueEnum};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
 #[derive(Debug, Parser)]
 #[command(version, about, long_about = None)]
 pub struct CliArgs {
-    #[arg(short, long, default_value = "127.0.0.1")]
-    pub addr: String,
-    #[arg(short, long, default_value = "2181")]
-    pub port: u16,
+    /// Zookeeper connect string, e.g. zk1:2181,zk2:2181/app
+    #[arg(short, long, default_value = "127.0.0.1:2181")]
+    pub connect: String,
+    /// Application mode, overrides the mode of the selected profile
+    #[arg(short, long, value_enum)]
+    pub mode: Option<AppMode>,
+    /// Digest credentials in user:password format, visible to other users in the process list
+    #[arg(long, env = "ZUI_AUTH", hide_env_values = true)]
+    pub auth: Option<String>,
+    /// File with the digest credentials in user:password format, wins over --auth
+    #[arg(long, value_name = "PATH")]
+    pub auth_file: Option<PathBuf>,
+    /// Zookeeper session timeout in milliseconds [default: 1000]
+    #[arg(long, value_name = "MS")]
+    pub session_timeout: Option<u64>,
+    /// Time in milliseconds to wait for the connection to be established [default: 3000]
+    #[arg(long, value_name = "MS")]
+    pub connect_timeout: Option<u64>,
+    /// Config file with connection profiles [default: $XDG_CONFIG_HOME/zui/config.json]
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+    /// Name of the connection profile to preselect
+    #[arg(short = 'P', long)]
+    pub profile: Option<String>,
+    /// Output format of the subcommands
+    #[arg(short, long, value_enum, global = true, default_value_t = OutputFormat::Text)]
+    pub output: OutputFormat,
+    /// Run a single operation and exit instead of launching the TUI
+    #[command(subcommand)]
+    pub command: Option<Command>,
 }
 
-pub(crate) fn parse_cli() -> CliArgs {
-    CliArgs::parse()
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// List node children
+    Ls { path: ZkPath },
+    /// Print node data
+    Get {
+        path: ZkPath,
+        #[arg(short, long, value_enum, default_value_t = DataFormat::String)]
+        format: DataFormat,
+    },
+    /// Print node stat
+    Stat { path: ZkPath },
+    /// Set node data, `-` reads the data from stdin
+    Set { path: ZkPath, data: String },
+    /// Create a node
+    Create {
+        path: ZkPath,
+        #[arg(default_value = "")]
+        data: String,
+        #[arg(short = 't', long = "type", value_name = "TYPE", value_enum, default_value_t = NodeCreateMode::Persistent)]
+        create_mode: NodeCreateMode,
+        /// TTL in milliseconds, required for persistent-with-ttl nodes
+        #[arg(long, value_name = "MS")]
+        ttl: Option<u64>,
+        /// ACL of the node, digest grants all permissions to the --auth credentials
+        #[arg(long, value_enum, default_value_t = AclPreset::Open)]
+        acl: AclPreset,
+    },
+    /// Delete a node
+    Rm {
+        path: ZkPath,
+        /// Delete the node together with all its descendants
+        #[arg(short, long)]
+        recursive: bool,
+    },
+    /// Print the subtree under the node
+    Tree { path: ZkPath },
+    /// Print the lines of node data matching the pattern in the subtree under the node
+    Grep {
+        pattern: String,
+        #[arg(default_value = "/")]
+        path: ZkPath,
+        #[arg(short, long, value_enum, default_value_t = PatternKind::Substring)]
+        kind: PatternKind,
+    },
+    /// Compare two subtrees, optionally on another cluster
+    Diff {
+        left: ZkPath,
+        right: ZkPath,
+        /// Connect string of the right subtree cluster, the main connection when omitted
+        #[arg(long, value_name = "CONNECT")]
+        right_connect: Option<String>,
+        /// Digest credentials for the right cluster, no authentication when omitted
+        #[arg(
+            long,
+            value_name = "AUTH",
+            env = "ZUI_RIGHT_AUTH",
+            hide_env_values = true
+        )]
+        right_auth: Option<String>,
+        /// File with the digest credentials for the right cluster, wins over --right-auth
+        #[arg(long, value_name = "PATH")]
+        right_auth_file: Option<PathBuf>,
+    },
+    /// Export the subtree under the node with data, stat and ACLs
+    Export {
+        path: ZkPath,
+        /// Snapshot file, YAML for .yaml/.yml and JSON otherwise; printed as JSON when omitted
+        file: Option<PathBuf>,
+    },
+    /// Create and update nodes under the target path from a snapshot file
+    Import {
+        file: PathBuf,
+        #[arg(default_value = "/")]
+        target: ZkPath,
+        #[arg(short, long, value_enum, default_value_t = ImportStrategy::SkipExisting)]
+        strategy: ImportStrategy,
+        /// Print the planned creates and updates without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+/// Node data representation, matches the `NodeData` variants.
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum DataFormat {
+    Raw,
+    String,
+    Json,
 }
 
-impl CliArgs {
-    pub(crate) fn connection(&self) -> Connection {
-        Connection {
-            addr: self.addr.parse().unwrap(), //TODO: handle error
-            port: self.port,
+pub(crate) fn parse_cli() -> CliArgs {
+    let mut cli = CliArgs::parse();
+    if let Some(path) = cli.auth_file.take() {
+        cli.auth = Some(read_auth_file(&path));
+    }
+    if let Some(Command::Diff {
+        right_auth,
+        right_auth_file,
+        ..
+    }) = &mut cli.command
+    {
+        if let Some(path) = right_auth_file.take() {
+            *right_auth = Some(read_auth_file(&path));
         }
     }
+    cli
+}
+
+/// Reads credentials without the trailing newline, exits like a usage error when unreadable.
+fn read_auth_file(path: &Path) -> String {
+    match fs::read_to_string(path) {
+        Ok(auth) => auth.trim_end().to_owned(),
+        Err(e) => CliArgs::command()
+            .error(
+                ErrorKind::Io,
+                format!("Failed to read {} : {e}", path.display()),
+            )
+            .exit(),
+    }
 }
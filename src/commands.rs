@@ -0,0 +1,475 @@
+use crate::{
+    app::{connection::Connection, recursive_delete::walk_subtree, App},
+    cli::{Command, DataFormat, OutputFormat},
+    diff::{DiffLine, SubtreeDiff},
+    errors::{zk_error_message, AppError},
+    grep::grep_subtree,
+    import::ImportPlan,
+    node_data::NodeData,
+    node_ops::{self, NodeOpError},
+    pattern::Pattern,
+    snapshot::Snapshot,
+    zk_path::ZkPath,
+};
+use serde_json::{json, Value};
+use std::{
+    io::{self, Read},
+    process::ExitCode,
+};
+use zookeeper_async::{Stat, ZkError, ZooKeeper};
+
+/// Exit codes of the non-interactive subcommands, `Usage` deliberately shares `2` with clap so
+/// invalid arguments exit the same way whether clap or the subcommand rejects them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExitStatus {
+    Success = 0,
+    Failure = 1,
+    Usage = 2,
+    NoNode = 3,
+    NodeExists = 4,
+    NoAuth = 5,
+    NotEmpty = 6,
+    ConnectionFailed = 7,
+    Forbidden = 8,
+    Differences = 9,
+    NoMatch = 10,
+}
+
+impl From<ExitStatus> for ExitCode {
+    fn from(status: ExitStatus) -> Self {
+        ExitCode::from(status as u8)
+    }
+}
+
+pub struct CommandError {
+    status: ExitStatus,
+    message: String,
+}
+
+impl CommandError {
+    fn new(status: ExitStatus, message: String) -> Self {
+        Self { status, message }
+    }
+
+    fn zk(action: &str, path: &str, e: ZkError) -> Self {
+        Self::new(zk_status(&e), zk_error_message(action, path, &e))
+    }
+}
+
+fn zk_status(e: &ZkError) -> ExitStatus {
+    match e {
+        ZkError::NoNode => ExitStatus::NoNode,
+        ZkError::NodeExists => ExitStatus::NodeExists,
+        ZkError::NoAuth => ExitStatus::NoAuth,
+        ZkError::NotEmpty => ExitStatus::NotEmpty,
+        _ => ExitStatus::Failure,
+    }
+}
+
+impl From<NodeOpError> for CommandError {
+    fn from(e: NodeOpError) -> Self {
+        let status = match e {
+            NodeOpError::Zk { ref error, .. } => zk_status(error),
+            NodeOpError::Invalid(_) => ExitStatus::Usage,
+        };
+        Self::new(status, e.to_string())
+    }
+}
+
+impl From<AppError> for CommandError {
+    fn from(e: AppError) -> Self {
+        let status = match e {
+            AppError::InvalidConnectionString(_)
+            | AppError::InvalidCredentials
+            | AppError::InvalidPath(..) => ExitStatus::Usage,
+            _ => ExitStatus::ConnectionFailed,
+        };
+        Self::new(status, e.to_string())
+    }
+}
+
+type CommandResult<T> = Result<T, CommandError>;
+
+/// Runs a subcommand against the connection configured for `app` and prints the result.
+pub async fn run(mut app: App, command: Command, output: OutputFormat) -> ExitCode {
+    match execute(&mut app, command).await {
+        Ok(result) => {
+            print_result(&result, output);
+            result.status.into()
+        }
+        Err(e) => {
+            match output {
+                OutputFormat::Text => eprintln!("{}", e.message),
+                OutputFormat::Json => {
+                    eprintln!("{}", json!({ "error": e.message, "code": e.status as u8 }))
+                }
+            }
+            e.status.into()
+        }
+    }
+}
+
+/// Command output: lines for the text format and a value for the JSON one.
+struct CommandOutput {
+    text: Vec<String>,
+    json: Value,
+    status: ExitStatus, // reported after printing, e.g. for partially failed imports
+}
+
+fn print_result(result: &CommandOutput, output: OutputFormat) {
+    match output {
+        OutputFormat::Text => result.text.iter().for_each(|line| println!("{line}")),
+        OutputFormat::Json => println!("{}", result.json),
+    }
+}
+
+async fn connect(app: &App) -> CommandResult<ZooKeeper> {
+    if app.connection.is_none() {
+        return Err(CommandError::new(
+            ExitStatus::Usage,
+            app.connection_error.clone().unwrap_or_default(),
+        ));
+    }
+    let zk = App::connect_default(
+        &app.connection_str(),
+        &app.auth_input,
+        app.session_timeout,
+        app.connect_timeout,
+        |_| {},
+    )
+    .await?;
+    Ok(zk)
+}
+
+fn ensure(allowed: bool, app: &App, action: &str) -> CommandResult<()> {
+    if allowed {
+        return Ok(());
+    }
+    Err(CommandError::new(
+        ExitStatus::Forbidden,
+        format!("{action} nodes is forbidden in {} mode", app.mode),
+    ))
+}
+
+async fn execute(app: &mut App, command: Command) -> CommandResult<CommandOutput> {
+    match command {
+        Command::Import { dry_run: true, .. } => {}
+        Command::Set { .. } | Command::Create { .. } | Command::Import { .. } => {
+            ensure(app.mode.can_write(), app, "Modifying")?
+        }
+        Command::Rm { .. } => ensure(app.mode.can_delete(), app, "Deleting")?,
+        _ => {}
+    }
+    let zk = connect(app).await?;
+
+    match command {
+        Command::Ls { path } => {
+            let mut children = zk
+                .get_children(&path, false)
+                .await
+                .map_err(|e| CommandError::zk("list children of", &path, e))?;
+            children.sort_unstable();
+            Ok(CommandOutput {
+                json: json!(children),
+                text: children,
+                status: ExitStatus::Success,
+            })
+        }
+        Command::Get { path, format } => {
+            let (data, _) = node_ops::get_data(&zk, &path, |_| {}).await?;
+            let data = match format {
+                DataFormat::Raw => NodeData::Raw(data),
+                DataFormat::String => NodeData::Raw(data).convert_to_string(),
+                DataFormat::Json => match NodeData::Raw(data).convert_to_json() {
+                    data @ NodeData::Json(_) => data,
+                    _ => {
+                        return Err(CommandError::new(
+                            ExitStatus::Failure,
+                            format!("Node {path} data is not valid JSON"),
+                        ))
+                    }
+                },
+            };
+            let json = match data {
+                NodeData::Raw(ref bytes) => json!(bytes),
+                NodeData::String(ref string) => json!(string),
+                NodeData::Json(ref value) => value.clone(),
+            };
+            Ok(CommandOutput {
+                text: vec![data.to_string()],
+                json: json!({ "path": path, "data": json }),
+                status: ExitStatus::Success,
+            })
+        }
+        Command::Stat { path } => {
+            let stat = zk
+                .exists(&path, false)
+                .await
+                .map_err(|e| CommandError::zk("read stat of", &path, e))?
+                .ok_or_else(|| CommandError::zk("read stat of", &path, ZkError::NoNode))?;
+            Ok(stat_output(&path, &stat))
+        }
+        Command::Set { path, data } => {
+            let data = read_data(data)?;
+            let stat = node_ops::set_data(&zk, &path, data.into_bytes()).await?;
+            Ok(CommandOutput {
+                text: vec![format!("Node {path} data successfully updated")],
+                json: json!({ "path": path, "version": stat.version }),
+                status: ExitStatus::Success,
+            })
+        }
+        Command::Create {
+            path,
+            data,
+            create_mode,
+            ttl,
+            acl,
+        } => {
+            let data = read_data(data)?.into_bytes();
+            let created_path =
+                node_ops::create(&zk, &path, data, create_mode, ttl, acl, &app.auth_input).await?;
+            Ok(CommandOutput {
+                text: vec![created_path.clone()],
+                json: json!({ "path": created_path }),
+                status: ExitStatus::Success,
+            })
+        }
+        Command::Rm { path, recursive } => {
+            let nodes = if recursive {
+                let mut nodes = walk_subtree(&zk, &path)
+                    .await
+                    .map_err(|(path, e)| CommandError::zk("walk", &path, e))?;
+                nodes.reverse();
+                nodes
+            } else {
+                vec![path]
+            };
+            for node in nodes.iter() {
+                node_ops::delete(&zk, node).await?;
+            }
+            Ok(CommandOutput {
+                json: json!({ "deleted": nodes }),
+                text: nodes.iter().map(ZkPath::to_string).collect(),
+                status: ExitStatus::Success,
+            })
+        }
+        Command::Export { path, file } => {
+            let snapshot = Snapshot::take(&zk, &path)
+                .await
+                .map_err(|(path, e)| CommandError::zk("export", &path, e))?;
+            let Some(file) = file else {
+                return Ok(CommandOutput {
+                    text: vec![snapshot.to_text(false)?],
+                    json: serde_json::to_value(&snapshot).map_err(AppError::from)?,
+                    status: ExitStatus::Success,
+                });
+            };
+            snapshot.save(&file).map_err(|e| {
+                CommandError::new(
+                    ExitStatus::Failure,
+                    format!("Failed to write {} : {e}", file.display()),
+                )
+            })?;
+            Ok(CommandOutput {
+                text: vec![format!(
+                    "Exported {} nodes under {path} to {}",
+                    snapshot.nodes.len(),
+                    file.display()
+                )],
+                json: json!({ "root": path, "file": file, "nodes": snapshot.nodes.len() }),
+                status: ExitStatus::Success,
+            })
+        }
+        Command::Import {
+            file,
+            target,
+            strategy,
+            dry_run,
+        } => {
+            let snapshot = Snapshot::load(&file).map_err(|e| {
+                CommandError::new(
+                    ExitStatus::Failure,
+                    format!("Failed to read {} : {e}", file.display()),
+                )
+            })?;
+            let chroot = app.connection.as_ref().and_then(|c| c.chroot.as_deref());
+            let mut plan = ImportPlan::build(&zk, chroot, &snapshot, &target, strategy).await;
+            if !dry_run {
+                plan.apply(&zk).await;
+            }
+
+            let status = if plan.has_conflicts() {
+                ExitStatus::NodeExists
+            } else if plan.failures().next().is_some() {
+                ExitStatus::Failure
+            } else {
+                ExitStatus::Success
+            };
+            let entries = plan
+                .entries
+                .iter()
+                .map(|entry| {
+                    json!({
+                        "path": entry.path,
+                        "action": entry.action.to_string(),
+                        "error": entry.error,
+                    })
+                })
+                .collect::<Vec<_>>();
+            let mut text = plan.lines().collect::<Vec<_>>();
+            text.push(plan.summary().trim_end().to_owned());
+            Ok(CommandOutput {
+                text,
+                json: json!({
+                    "target": target,
+                    "dry_run": dry_run,
+                    "applied": plan.applied,
+                    "entries": entries,
+                }),
+                status,
+            })
+        }
+        Command::Diff {
+            left,
+            right,
+            right_connect,
+            right_auth,
+            ..
+        } => {
+            let left = Snapshot::take(&zk, &left)
+                .await
+                .map_err(|(path, e)| CommandError::zk("read", &path, e))?;
+            let right = match right_connect {
+                Some(connect) => {
+                    let connection = connect.parse::<Connection>()?;
+                    let right_zk = App::connect_default(
+                        &connection.to_string(),
+                        right_auth.as_deref().unwrap_or_default(),
+                        app.session_timeout,
+                        app.connect_timeout,
+                        |_| {},
+                    )
+                    .await?;
+                    let right = Snapshot::take(&right_zk, &right).await;
+                    let _ = right_zk.close().await;
+                    right
+                }
+                None => Snapshot::take(&zk, &right).await,
+            }
+            .map_err(|(path, e)| CommandError::zk("read", &path, e))?;
+
+            let diff = SubtreeDiff::compare(&left, &right);
+            let mut text = diff
+                .lines()
+                .iter()
+                .map(DiffLine::to_string)
+                .collect::<Vec<_>>();
+            text.push(diff.summary());
+            let entries = diff
+                .entries
+                .iter()
+                .map(|entry| {
+                    json!({
+                        "path": entry.path,
+                        "kind": entry.kind.to_string(),
+                        "left": entry.left,
+                        "right": entry.right,
+                    })
+                })
+                .collect::<Vec<_>>();
+            Ok(CommandOutput {
+                text,
+                json: json!({
+                    "left": diff.left_root,
+                    "right": diff.right_root,
+                    "entries": entries,
+                }),
+                status: if diff.entries.is_empty() {
+                    ExitStatus::Success
+                } else {
+                    ExitStatus::Differences
+                },
+            })
+        }
+        Command::Tree { path } => {
+            let nodes = walk_subtree(&zk, &path)
+                .await
+                .map_err(|(path, e)| CommandError::zk("walk", &path, e))?;
+            let text = nodes
+                .iter()
+                .map(|node| {
+                    let name = Some(node.basename()).filter(|name| !name.is_empty());
+                    let indent = "  ".repeat(node.depth() - path.depth());
+                    format!("{indent}{}", name.unwrap_or(node))
+                })
+                .collect();
+            Ok(CommandOutput {
+                text,
+                json: json!(nodes),
+                status: ExitStatus::Success,
+            })
+        }
+        Command::Grep {
+            pattern,
+            path,
+            kind,
+        } => {
+            let pattern = Pattern::new(kind, &pattern)
+                .map_err(|e| CommandError::new(ExitStatus::Usage, e))?;
+            let matches = grep_subtree(&zk, &path, &pattern)
+                .await
+                .map_err(|(path, e)| CommandError::zk("grep", &path, e))?;
+            Ok(CommandOutput {
+                text: matches
+                    .iter()
+                    .map(|m| format!("{}:{}: {}", m.path, m.line, m.context))
+                    .collect(),
+                json: json!(matches),
+                status: if matches.is_empty() {
+                    ExitStatus::NoMatch
+                } else {
+                    ExitStatus::Success
+                },
+            })
+        }
+    }
+}
+
+fn read_data(data: String) -> CommandResult<String> {
+    if data != "-" {
+        return Ok(data);
+    }
+    let mut buf = String::new();
+    io::stdin().read_to_string(&mut buf).map_err(|e| {
+        CommandError::new(ExitStatus::Failure, format!("Failed to read stdin : {e}"))
+    })?;
+    Ok(buf)
+}
+
+fn stat_output(path: &str, stat: &Stat) -> CommandOutput {
+    let fields = [
+        ("czxid", stat.czxid),
+        ("mzxid", stat.mzxid),
+        ("ctime", stat.ctime),
+        ("mtime", stat.mtime),
+        ("version", i64::from(stat.version)),
+        ("cversion", i64::from(stat.cversion)),
+        ("aversion", i64::from(stat.aversion)),
+        ("ephemeral_owner", stat.ephemeral_owner),
+        ("data_length", i64::from(stat.data_length)),
+        ("num_children", i64::from(stat.num_children)),
+        ("pzxid", stat.pzxid),
+    ];
+    let mut json = json!({ "path": path });
+    for (name, value) in fields.iter() {
+        json[*name] = json!(value);
+    }
+    CommandOutput {
+        text: fields
+            .iter()
+            .map(|(name, value)| format!("{name} : {value}"))
+            .collect(),
+        json,
+        status: ExitStatus::Success,
+    }
+}
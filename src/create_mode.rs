@@ -0,0 +1,72 @@
+use clap::ValueEnum;
+use core::fmt;
+use zookeeper_async::CreateMode;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum NodeCreateMode {
+    #[default]
+    Persistent,
+    Ephemeral,
+    PersistentSequential,
+    EphemeralSequential,
+    Container,
+    PersistentWithTtl,
+}
+
+impl NodeCreateMode {
+    const ALL: [NodeCreateMode; 6] = [
+        NodeCreateMode::Persistent,
+        NodeCreateMode::Ephemeral,
+        NodeCreateMode::PersistentSequential,
+        NodeCreateMode::EphemeralSequential,
+        NodeCreateMode::Container,
+        NodeCreateMode::PersistentWithTtl,
+    ];
+
+    fn position(&self) -> usize {
+        NodeCreateMode::ALL
+            .iter()
+            .position(|m| m == self)
+            .unwrap_or_default()
+    }
+
+    pub fn next(self) -> Self {
+        NodeCreateMode::ALL[(self.position() + 1) % NodeCreateMode::ALL.len()]
+    }
+
+    pub fn previous(self) -> Self {
+        let len = NodeCreateMode::ALL.len();
+        NodeCreateMode::ALL[(self.position() + len - 1) % len]
+    }
+
+    pub fn requires_ttl(&self) -> bool {
+        matches!(self, NodeCreateMode::PersistentWithTtl)
+    }
+}
+
+impl From<NodeCreateMode> for CreateMode {
+    fn from(mode: NodeCreateMode) -> Self {
+        match mode {
+            NodeCreateMode::Persistent => CreateMode::Persistent,
+            NodeCreateMode::Ephemeral => CreateMode::Ephemeral,
+            NodeCreateMode::PersistentSequential => CreateMode::PersistentSequential,
+            NodeCreateMode::EphemeralSequential => CreateMode::EphemeralSequential,
+            NodeCreateMode::Container => CreateMode::Container,
+            NodeCreateMode::PersistentWithTtl => CreateMode::PersistentWithTTL,
+        }
+    }
+}
+
+impl fmt::Display for NodeCreateMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            NodeCreateMode::Persistent => "Persistent",
+            NodeCreateMode::Ephemeral => "Ephemeral",
+            NodeCreateMode::PersistentSequential => "Persistent Sequential",
+            NodeCreateMode::EphemeralSequential => "Ephemeral Sequential",
+            NodeCreateMode::Container => "Container",
+            NodeCreateMode::PersistentWithTtl => "Persistent with TTL",
+        };
+        write!(f, "{name}")
+    }
+}
@@ -0,0 +1,278 @@
+use crate::{
+    node_data::NodeData,
+    snapshot::{Snapshot, SnapshotData},
+};
+use core::fmt;
+use similar::{ChangeTag, TextDiff};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DiffKind {
+    Added,   // only in the right subtree
+    Removed, // only in the left subtree
+    Changed,
+}
+
+impl DiffKind {
+    pub fn marker(&self) -> char {
+        match self {
+            DiffKind::Added => '+',
+            DiffKind::Removed => '-',
+            DiffKind::Changed => '~',
+        }
+    }
+}
+
+impl fmt::Display for DiffKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            DiffKind::Added => "added",
+            DiffKind::Removed => "removed",
+            DiffKind::Changed => "changed",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DiffEntry {
+    pub path: String, // relative to the compared roots, `/` for the roots themselves
+    pub kind: DiffKind,
+    pub left: Option<SnapshotData>,
+    pub right: Option<SnapshotData>,
+}
+
+/// Line of the rendered diff.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiffLine {
+    Node(DiffKind, String),
+    Removed(String),
+    Added(String),
+    Same(String),
+}
+
+impl fmt::Display for DiffLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiffLine::Node(kind, path) => write!(f, "{} {path} ({kind})", kind.marker()),
+            DiffLine::Removed(line) => write!(f, "    -{line}"),
+            DiffLine::Added(line) => write!(f, "    +{line}"),
+            DiffLine::Same(line) => write!(f, "     {line}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SubtreeDiff {
+    pub left_root: String,
+    pub right_root: String,
+    pub entries: Vec<DiffEntry>,
+}
+
+fn relative_path(root: &str, path: &str) -> String {
+    match path.strip_prefix(root) {
+        Some("") | None => "/".to_owned(),
+        Some(_) if root == "/" => path.to_owned(),
+        Some(relative) => relative.to_owned(),
+    }
+}
+
+/// Data as shown in the node data view: pretty JSON when it parses, text otherwise.
+pub fn data_text(data: &SnapshotData) -> String {
+    let Ok(bytes) = data.decode() else {
+        return String::new();
+    };
+    match NodeData::Raw(bytes).convert_to_json() {
+        NodeData::Json(value) => serde_json::to_string_pretty(&value).unwrap_or_default(),
+        data => data.convert_to_string().to_string(),
+    }
+}
+
+impl SubtreeDiff {
+    pub fn compare(left: &Snapshot, right: &Snapshot) -> Self {
+        let mut nodes: BTreeMap<String, (Option<&SnapshotData>, Option<&SnapshotData>)> =
+            BTreeMap::new();
+        for node in left.nodes.iter() {
+            nodes
+                .entry(relative_path(&left.root, &node.path))
+                .or_default()
+                .0 = Some(&node.data);
+        }
+        for node in right.nodes.iter() {
+            nodes
+                .entry(relative_path(&right.root, &node.path))
+                .or_default()
+                .1 = Some(&node.data);
+        }
+
+        let entries = nodes
+            .into_iter()
+            .filter_map(|(path, sides)| {
+                let kind = match sides {
+                    (Some(_), None) => DiffKind::Removed,
+                    (None, Some(_)) => DiffKind::Added,
+                    (Some(l), Some(r)) if l.decode() != r.decode() => DiffKind::Changed,
+                    _ => return None,
+                };
+                Some(DiffEntry {
+                    path,
+                    kind,
+                    left: sides.0.cloned(),
+                    right: sides.1.cloned(),
+                })
+            })
+            .collect();
+
+        Self {
+            left_root: left.root.clone(),
+            right_root: right.root.clone(),
+            entries,
+        }
+    }
+
+    fn count(&self, kind: DiffKind) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| entry.kind == kind)
+            .count()
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "{} added, {} removed, {} changed between {} and {}",
+            self.count(DiffKind::Added),
+            self.count(DiffKind::Removed),
+            self.count(DiffKind::Changed),
+            self.left_root,
+            self.right_root
+        )
+    }
+
+    /// Node headers followed by the line diff of the data for changed nodes.
+    pub fn lines(&self) -> Vec<DiffLine> {
+        let mut lines = Vec::new();
+        for entry in self.entries.iter() {
+            lines.push(DiffLine::Node(entry.kind, entry.path.clone()));
+            let (Some(left), Some(right)) = (&entry.left, &entry.right) else {
+                continue;
+            };
+            let (left, right) = (data_text(left), data_text(right));
+            let diff = TextDiff::from_lines(&left, &right);
+            lines.extend(diff.iter_all_changes().map(|change| {
+                let line = change.value().trim_end_matches('\n').to_owned();
+                match change.tag() {
+                    ChangeTag::Delete => DiffLine::Removed(line),
+                    ChangeTag::Insert => DiffLine::Added(line),
+                    ChangeTag::Equal => DiffLine::Same(line),
+                }
+            }));
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::{SnapshotNode, SnapshotStat};
+
+    fn snapshot(root: &str, nodes: &[(&str, &str)]) -> Snapshot {
+        let stat = SnapshotStat {
+            czxid: 0,
+            mzxid: 0,
+            ctime: 0,
+            mtime: 0,
+            version: 0,
+            cversion: 0,
+            aversion: 0,
+            ephemeral_owner: 0,
+            data_length: 0,
+            num_children: 0,
+            pzxid: 0,
+        };
+        Snapshot {
+            root: root.to_owned(),
+            nodes: nodes
+                .iter()
+                .map(|(path, data)| SnapshotNode {
+                    path: path.to_string(),
+                    data: SnapshotData::encode(data.as_bytes()),
+                    stat: stat.clone(),
+                    acl: Vec::new(),
+                })
+                .collect(),
+        }
+    }
+
+    fn kinds(diff: &SubtreeDiff) -> Vec<(&str, DiffKind)> {
+        diff.entries
+            .iter()
+            .map(|entry| (entry.path.as_str(), entry.kind))
+            .collect()
+    }
+
+    #[test]
+    fn compares_nodes_relative_to_roots() {
+        let left = snapshot(
+            "/prod",
+            &[
+                ("/prod", ""),
+                ("/prod/a", "1"),
+                ("/prod/b", "x"),
+                ("/prod/c", "same"),
+            ],
+        );
+        let right = snapshot(
+            "/staging",
+            &[
+                ("/staging", ""),
+                ("/staging/b", "y"),
+                ("/staging/c", "same"),
+                ("/staging/d", "new"),
+            ],
+        );
+        let diff = SubtreeDiff::compare(&left, &right);
+        assert_eq!(
+            kinds(&diff),
+            vec![
+                ("/a", DiffKind::Removed),
+                ("/b", DiffKind::Changed),
+                ("/d", DiffKind::Added)
+            ]
+        );
+        assert_eq!(
+            diff.summary(),
+            "1 added, 1 removed, 1 changed between /prod and /staging"
+        );
+    }
+
+    #[test]
+    fn compares_roots_and_root_relative_paths() {
+        let left = snapshot("/", &[("/", "root"), ("/a", "1")]);
+        let right = snapshot("/copy", &[("/copy", "other"), ("/copy/a", "1")]);
+        let diff = SubtreeDiff::compare(&left, &right);
+        assert_eq!(kinds(&diff), vec![("/", DiffKind::Changed)]);
+    }
+
+    #[test]
+    fn identical_subtrees_have_no_entries() {
+        let left = snapshot("/a", &[("/a", "{\"k\":1}"), ("/a/b", "text")]);
+        let right = snapshot("/b", &[("/b", "{\"k\":1}"), ("/b/b", "text")]);
+        assert!(SubtreeDiff::compare(&left, &right).entries.is_empty());
+    }
+
+    #[test]
+    fn lists_data_line_changes() {
+        let left = snapshot("/a", &[("/a", "one\ntwo")]);
+        let right = snapshot("/b", &[("/b", "one\nthree")]);
+        assert_eq!(
+            SubtreeDiff::compare(&left, &right).lines(),
+            vec![
+                DiffLine::Node(DiffKind::Changed, "/".to_owned()),
+                DiffLine::Same("one".to_owned()),
+                DiffLine::Removed("two".to_owned()),
+                DiffLine::Added("three".to_owned()),
+            ]
+        );
+    }
+}
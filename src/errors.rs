@@ -1,13 +1,45 @@
+use std::time::Duration;
+use zookeeper_async::ZkError;
+
 #[derive(Debug, thiserror::Error)]
 pub enum AppError {
     #[error(transparent)]
-    ZkError(#[from] zookeeper_async::ZkError),
+    ZkError(#[from] ZkError),
 
     #[error(transparent)]
     IoError(#[from] std::io::Error),
 
-    #[error("Establish connection timeout")]
-    ConnectionTimeoutError,
+    #[error(transparent)]
+    JsonError(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    YamlError(#[from] serde_yaml::Error),
+
+    #[error("Establish connection timeout: no response within {0:?}")]
+    ConnectionTimeoutError(Duration),
+
+    #[error(
+        "Digest credentials must be in user:password format with a non-empty user and password"
+    )]
+    InvalidCredentials,
+
+    #[error("Invalid connection string: {0}")]
+    InvalidConnectionString(String),
+
+    #[error("Invalid path {0} : {1}")]
+    InvalidPath(String, String),
+
+    #[error("Invalid key bindings: {0}")]
+    InvalidKeyBindings(String),
 }
 
 pub type AppResult<T> = Result<T, AppError>;
+
+pub fn zk_error_message(action: &str, path: &str, e: &ZkError) -> String {
+    match e {
+        ZkError::NoAuth => {
+            format!("Not authorised to {action} {path}, check the connection digest credentials")
+        }
+        _ => format!("Failed to {action} {path} : {e}"),
+    }
+}
@@ -0,0 +1,41 @@
+use crossterm::event::{Event, EventStream};
+use futures::StreamExt;
+use std::time::Duration;
+use tokio::{sync::mpsc::UnboundedSender, time};
+use zookeeper_async::WatchedEvent;
+
+pub const TICK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Everything the event loop reacts to, delivered through a single channel.
+#[derive(Debug)]
+pub enum AppEvent {
+    Input(Event),
+    Tick, // drives the spinner, reconnects and recursive delete batches
+    Watch {
+        cluster: usize,
+        generation: u64,
+        event: WatchedEvent,
+    },
+}
+
+pub type EventSender = UnboundedSender<AppEvent>;
+
+/// Forwards terminal input and ticks to the event loop until it stops listening.
+pub fn spawn_input_task(tx: EventSender) {
+    tokio::spawn(async move {
+        let mut input = EventStream::new();
+        let mut tick = time::interval(TICK_INTERVAL);
+        loop {
+            let event = tokio::select! {
+                _ = tick.tick() => AppEvent::Tick,
+                event = input.next() => match event {
+                    Some(Ok(event)) => AppEvent::Input(event),
+                    _ => break,
+                },
+            };
+            if tx.send(event).is_err() {
+                break;
+            }
+        }
+    });
+}
@@ -0,0 +1,112 @@
+use crate::{
+    app::recursive_delete::walk_subtree, node_data::NodeData, pattern::Pattern, zk_path::ZkPath,
+};
+use serde::Serialize;
+use std::ops::Range;
+use zookeeper_async::{ZkError, ZooKeeper};
+
+const CONTEXT_SIZE: usize = 120;
+
+/// Line of node data matching a grep pattern.
+#[derive(Debug, Clone, Serialize)]
+pub struct GrepMatch {
+    pub path: ZkPath,
+    pub line: usize, // 1-based
+    pub context: String,
+}
+
+/// Trims the matched line and cuts it to `CONTEXT_SIZE` characters centered on the match
+/// at byte range `found`.
+fn context(line: &str, found: Range<usize>) -> String {
+    let trimmed = line.trim();
+    let lead = line.len() - line.trim_start().len();
+    let chars_to = |end: usize| {
+        trimmed[..end.saturating_sub(lead).min(trimmed.len())]
+            .chars()
+            .count()
+    };
+    let total = trimmed.chars().count();
+    if total <= CONTEXT_SIZE {
+        return trimmed.to_owned();
+    }
+    let middle = (chars_to(found.start) + chars_to(found.end)) / 2;
+    let start = middle
+        .saturating_sub(CONTEXT_SIZE / 2)
+        .min(total - CONTEXT_SIZE);
+    let end = start + CONTEXT_SIZE;
+    let byte_at = |n: usize| {
+        trimmed
+            .char_indices()
+            .nth(n)
+            .map_or(trimmed.len(), |(i, _)| i)
+    };
+    let mut context = String::new();
+    if start > 0 {
+        context.push('…');
+    }
+    context.push_str(&trimmed[byte_at(start)..byte_at(end)]);
+    if end < total {
+        context.push('…');
+    }
+    context
+}
+
+/// Finds the lines matching `pattern` in the data of `root` and all its descendants.
+/// Nodes deleted while walking are skipped.
+pub async fn grep_subtree(
+    zk: &ZooKeeper,
+    root: &ZkPath,
+    pattern: &Pattern,
+) -> Result<Vec<GrepMatch>, (ZkPath, ZkError)> {
+    let mut matches = Vec::new();
+    for path in walk_subtree(zk, root).await? {
+        let data = match zk.get_data(&path, false).await {
+            Ok((data, _)) => data,
+            Err(ZkError::NoNode) => continue,
+            Err(e) => return Err((path, e)),
+        };
+        let data = NodeData::Raw(data).convert_to_string().to_string();
+        matches.extend(data.lines().enumerate().filter_map(|(index, line)| {
+            pattern.find(line).map(|found| GrepMatch {
+                path: path.clone(),
+                line: index + 1,
+                context: context(line, found),
+            })
+        }));
+    }
+    Ok(matches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_line_is_trimmed() {
+        assert_eq!(context("  key: value \t", 2..5), "key: value");
+    }
+
+    #[test]
+    fn long_line_is_centered_on_match() {
+        let line = format!("{}needle{}", "a".repeat(200), "b".repeat(200));
+        let shown = context(&line, 200..206);
+        assert_eq!(shown.chars().count(), CONTEXT_SIZE + 2);
+        assert!(shown.starts_with("…a"), "{shown}");
+        assert!(shown.ends_with("b…"), "{shown}");
+        let needle = shown.find("needle").unwrap();
+        assert_eq!(shown[..needle].chars().count(), 1 + 57);
+    }
+
+    #[test]
+    fn window_stays_within_the_line() {
+        let line = format!("  {}é needle", "é".repeat(200));
+        let found = line.find("needle").unwrap();
+        let shown = context(&line, found..found + 6);
+        assert_eq!(shown.chars().count(), CONTEXT_SIZE + 1);
+        assert!(shown.starts_with('…'));
+        assert!(shown.ends_with("é needle"), "{shown}");
+        let shown = context(&line, 2..4);
+        assert!(shown.starts_with("éé"));
+        assert!(shown.ends_with('…'));
+    }
+}
@@ -0,0 +1,326 @@
+use crate::{
+    errors::{AppError, AppResult},
+    snapshot::{Snapshot, SnapshotNode},
+    zk_path::ZkPath,
+};
+use clap::ValueEnum;
+use core::fmt;
+use zookeeper_async::{Acl, CreateMode, ZkError, ZooKeeper};
+
+pub const SYSTEM_NODE: &str = "/zookeeper";
+
+/// What to do with snapshot nodes that already exist in the target subtree.
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum ImportStrategy {
+    /// Keep existing nodes untouched and only create the missing ones
+    #[default]
+    SkipExisting,
+    /// Replace the data of existing nodes
+    Overwrite,
+    /// Write nothing if any snapshot node already exists with different data
+    FailOnConflict,
+}
+
+impl ImportStrategy {
+    const ALL: [ImportStrategy; 3] = [
+        ImportStrategy::SkipExisting,
+        ImportStrategy::Overwrite,
+        ImportStrategy::FailOnConflict,
+    ];
+
+    pub fn next(self) -> Self {
+        let position = ImportStrategy::ALL
+            .iter()
+            .position(|s| *s == self)
+            .unwrap_or_default();
+        ImportStrategy::ALL[(position + 1) % ImportStrategy::ALL.len()]
+    }
+}
+
+impl fmt::Display for ImportStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ImportStrategy::SkipExisting => "Skip existing",
+            ImportStrategy::Overwrite => "Overwrite",
+            ImportStrategy::FailOnConflict => "Fail on conflict",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImportAction {
+    Create,
+    Update,
+    Unchanged,
+    Skip,
+    Conflict,
+}
+
+impl fmt::Display for ImportAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ImportAction::Create => "create",
+            ImportAction::Update => "update",
+            ImportAction::Unchanged => "unchanged",
+            ImportAction::Skip => "skip",
+            ImportAction::Conflict => "conflict",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportEntry {
+    pub path: ZkPath, // path in the target subtree
+    pub action: ImportAction,
+    pub data: Vec<u8>,
+    pub acl: Vec<Acl>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ImportPlan {
+    pub target: String,
+    pub strategy: ImportStrategy,
+    pub entries: Vec<ImportEntry>, // parents always go before their children
+    pub applied: bool,
+}
+
+/// Maps a snapshot node path from the snapshot root onto `target`.
+pub fn target_path(snapshot_root: &str, target: &ZkPath, path: &str) -> AppResult<ZkPath> {
+    let relative = match path.strip_prefix(snapshot_root) {
+        Some(relative) if snapshot_root == "/" => Some(relative),
+        Some("") => Some(""),
+        Some(relative) => relative.strip_prefix('/'),
+        None => None,
+    }
+    .ok_or_else(|| {
+        AppError::InvalidPath(
+            path.to_owned(),
+            format!("not under the snapshot root {snapshot_root}"),
+        )
+    })?;
+    relative
+        .split('/')
+        .filter(|_| !relative.is_empty())
+        .try_fold(target.clone(), |path, name| path.join(name))
+}
+
+/// Whether `path`, relative to the connection `chroot`, is in the zookeeper system subtree.
+fn is_system_node(path: &str, chroot: Option<&str>) -> bool {
+    let path = format!("{}{path}", chroot.unwrap_or_default());
+    path == SYSTEM_NODE || path.starts_with(&format!("{SYSTEM_NODE}/"))
+}
+
+async fn plan_entry(
+    zk: &ZooKeeper,
+    node: &SnapshotNode,
+    path: ZkPath,
+    chroot: Option<&str>,
+    strategy: ImportStrategy,
+) -> ImportEntry {
+    let mut entry = ImportEntry {
+        path,
+        action: ImportAction::Skip,
+        data: Vec::new(),
+        acl: Vec::new(),
+        error: None,
+    };
+    if is_system_node(&entry.path, chroot) {
+        entry.error = Some("system node".to_owned());
+        return entry;
+    }
+    if node.stat.ephemeral_owner != 0 {
+        entry.error = Some("ephemeral node".to_owned());
+        return entry;
+    }
+
+    let decoded = node.data.decode().and_then(|data| {
+        let acl = node
+            .acl
+            .iter()
+            .map(|acl| acl.to_acl())
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok((data, acl))
+    });
+    match decoded {
+        Ok((data, acl)) => {
+            entry.data = data;
+            entry.acl = acl;
+        }
+        Err(e) => {
+            entry.error = Some(e);
+            return entry;
+        }
+    }
+
+    entry.action = match zk.get_data(&entry.path, false).await {
+        Err(ZkError::NoNode) => ImportAction::Create,
+        Err(e) => {
+            entry.error = Some(e.to_string());
+            ImportAction::Skip
+        }
+        Ok((data, _)) if data == entry.data => ImportAction::Unchanged,
+        Ok(_) => match strategy {
+            ImportStrategy::SkipExisting => ImportAction::Skip,
+            ImportStrategy::Overwrite => ImportAction::Update,
+            ImportStrategy::FailOnConflict => ImportAction::Conflict,
+        },
+    };
+    entry
+}
+
+impl ImportPlan {
+    /// Compares every snapshot node with the target subtree without writing anything.
+    /// `chroot` is the one of the `zk` connection.
+    pub async fn build(
+        zk: &ZooKeeper,
+        chroot: Option<&str>,
+        snapshot: &Snapshot,
+        target: &ZkPath,
+        strategy: ImportStrategy,
+    ) -> Self {
+        let mut entries = Vec::with_capacity(snapshot.nodes.len());
+        for node in snapshot.nodes.iter() {
+            let entry = match target_path(&snapshot.root, target, &node.path) {
+                Ok(path) => plan_entry(zk, node, path, chroot, strategy).await,
+                Err(e) => ImportEntry {
+                    path: target.clone(),
+                    action: ImportAction::Skip,
+                    data: Vec::new(),
+                    acl: Vec::new(),
+                    error: Some(e.to_string()),
+                },
+            };
+            entries.push(entry);
+        }
+        Self {
+            target: target.to_string(),
+            strategy,
+            entries,
+            applied: false,
+        }
+    }
+
+    pub fn has_conflicts(&self) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| entry.action == ImportAction::Conflict)
+    }
+
+    /// Writes the planned creates and updates, recording per-node failures in the entries.
+    pub async fn apply(&mut self, zk: &ZooKeeper) {
+        if self.has_conflicts() {
+            return;
+        }
+        for entry in self
+            .entries
+            .iter_mut()
+            .filter(|entry| entry.error.is_none())
+        {
+            let res = match entry.action {
+                ImportAction::Create => zk
+                    .create(
+                        &entry.path,
+                        entry.data.clone(),
+                        entry.acl.clone(),
+                        CreateMode::Persistent,
+                    )
+                    .await
+                    .map(|_| ()),
+                ImportAction::Update => zk
+                    .set_data(&entry.path, entry.data.clone(), None)
+                    .await
+                    .map(|_| ()),
+                _ => Ok(()),
+            };
+            if let Err(e) = res {
+                entry.error = Some(e.to_string());
+            }
+        }
+        self.applied = true;
+    }
+
+    fn count(&self, action: ImportAction) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| entry.action == action && entry.error.is_none())
+            .count()
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &ImportEntry> {
+        self.entries.iter().filter(|entry| entry.error.is_some())
+    }
+
+    /// One line per node: the planned action, or the failure reason.
+    pub fn lines(&self) -> impl Iterator<Item = String> + '_ {
+        self.entries.iter().map(|entry| match entry.error {
+            Some(ref e) => format!("failed {} : {e}", entry.path),
+            None => format!("{} {}", entry.action, entry.path),
+        })
+    }
+
+    pub fn summary(&self) -> String {
+        let mut summary =
+            if self.has_conflicts() {
+                format!(
+                    "Import into {} aborted, {} nodes already exist with different data\n",
+                    self.target,
+                    self.count(ImportAction::Conflict)
+                )
+            } else {
+                format!(
+                "{} into {} ({}): {} created, {} updated, {} unchanged, {} skipped, {} failed\n",
+                if self.applied { "Imported" } else { "Import plan" },
+                self.target,
+                self.strategy,
+                self.count(ImportAction::Create),
+                self.count(ImportAction::Update),
+                self.count(ImportAction::Unchanged),
+                self.count(ImportAction::Skip),
+                self.failures().count()
+            )
+            };
+        for entry in self.failures() {
+            summary.push_str(&format!(
+                "Failed {} : {}\n",
+                entry.path,
+                entry.error.as_deref().unwrap_or_default()
+            ));
+        }
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_snapshot_paths_onto_target() {
+        let target = ZkPath::parse("/restore").unwrap();
+        let mapped = |root, path| target_path(root, &target, path).map(|path| path.to_string());
+        assert_eq!(mapped("/app", "/app").unwrap(), "/restore");
+        assert_eq!(
+            mapped("/app", "/app/config/db").unwrap(),
+            "/restore/config/db"
+        );
+        assert_eq!(mapped("/", "/").unwrap(), "/restore");
+        assert_eq!(mapped("/", "/app").unwrap(), "/restore/app");
+        assert!(mapped("/app", "/apple").is_err());
+        assert!(mapped("/app", "/other").is_err());
+        assert!(mapped("/app", "/app/../etc").is_err());
+        assert!(mapped("/app", "/app//config").is_err());
+    }
+
+    #[test]
+    fn system_node_follows_chroot() {
+        assert!(is_system_node("/zookeeper", None));
+        assert!(is_system_node("/zookeeper/quota", None));
+        assert!(!is_system_node("/zookeeper2", None));
+        assert!(!is_system_node("/zookeeper", Some("/app")));
+        assert!(is_system_node("/", Some("/zookeeper")));
+        assert!(is_system_node("/quota", Some("/zookeeper")));
+    }
+}
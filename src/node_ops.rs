@@ -0,0 +1,80 @@
+use crate::{
+    acl::AclPreset, create_mode::NodeCreateMode, errors::zk_error_message, zk_path::ZkPath,
+};
+use std::time::Duration;
+use zookeeper_async::{Stat, Watcher, ZkError, ZooKeeper};
+
+/// A failed node operation, its message is shown as is by the UI and the subcommands.
+#[derive(Debug, thiserror::Error)]
+pub enum NodeOpError {
+    #[error("{}", zk_error_message(.action, .path, .error))]
+    Zk {
+        action: &'static str,
+        path: ZkPath,
+        error: ZkError,
+    },
+
+    #[error("{0}")]
+    Invalid(String),
+}
+
+impl NodeOpError {
+    fn zk(action: &'static str, path: &ZkPath) -> impl FnOnce(ZkError) -> Self {
+        let path = path.clone();
+        move |error| NodeOpError::Zk {
+            action,
+            path,
+            error,
+        }
+    }
+}
+
+pub type NodeOpResult<T> = Result<T, NodeOpError>;
+
+pub async fn get_data(
+    zk: &ZooKeeper,
+    path: &ZkPath,
+    watcher: impl Watcher + 'static,
+) -> NodeOpResult<(Vec<u8>, Stat)> {
+    zk.get_data_w(path, watcher)
+        .await
+        .map_err(NodeOpError::zk("read data of", path))
+}
+
+pub async fn set_data(zk: &ZooKeeper, path: &ZkPath, data: Vec<u8>) -> NodeOpResult<Stat> {
+    zk.set_data(path, data, None)
+        .await
+        .map_err(NodeOpError::zk("update data of", path))
+}
+
+pub async fn delete(zk: &ZooKeeper, path: &ZkPath) -> NodeOpResult<()> {
+    zk.delete(path, None)
+        .await
+        .map_err(NodeOpError::zk("delete", path))
+}
+
+/// Creates the node with the preset ACL, `ttl` is in milliseconds and only used by TTL modes.
+pub async fn create(
+    zk: &ZooKeeper,
+    path: &ZkPath,
+    data: Vec<u8>,
+    mode: NodeCreateMode,
+    ttl: Option<u64>,
+    acl: AclPreset,
+    credentials: &str,
+) -> NodeOpResult<String> {
+    let acl = acl.acl(credentials).map_err(NodeOpError::Invalid)?;
+    let res = match (mode.requires_ttl(), ttl) {
+        (true, Some(ttl)) => {
+            zk.create_ttl(path, data, acl, mode.into(), Duration::from_millis(ttl))
+                .await
+        }
+        (true, None) => {
+            return Err(NodeOpError::Invalid(format!(
+                "{mode} node requires a TTL in milliseconds"
+            )))
+        }
+        (false, _) => zk.create(path, data, acl, mode.into()).await,
+    };
+    res.map_err(NodeOpError::zk("create", path))
+}
@@ -0,0 +1,57 @@
+use crate::app::App;
+use futures::future::LocalBoxFuture;
+use std::{
+    ops::ControlFlow,
+    time::{Duration, Instant},
+};
+use tokio::sync::oneshot;
+
+/// Operations finishing faster than this never show the spinner.
+pub const SPINNER_DELAY: Duration = Duration::from_millis(200);
+
+/// Output of an operation: the app it borrowed, and whether to keep running or `None` when cancelled.
+pub type Finished = (App, Option<ControlFlow<()>>);
+
+/// Event handling running alongside the event loop. It owns the app until it finishes,
+/// the loop meanwhile keeps showing the last frame with a spinner and listens for cancelling.
+pub struct Operation {
+    pub started: Instant,
+    cancel: Option<oneshot::Sender<()>>,
+    future: LocalBoxFuture<'static, Finished>,
+}
+
+impl Operation {
+    pub fn start<F>(mut app: App, handler: F) -> Self
+    where
+        F: for<'a> FnOnce(&'a mut App) -> LocalBoxFuture<'a, ControlFlow<()>> + 'static,
+    {
+        let (cancel, cancelled) = oneshot::channel();
+        let future = Box::pin(async move {
+            let flow = tokio::select! {
+                flow = handler(&mut app) => Some(flow),
+                _ = cancelled => None,
+            };
+            (app, flow)
+        });
+        Self {
+            started: Instant::now(),
+            cancel: Some(cancel),
+            future,
+        }
+    }
+
+    /// Drops the handler at its next await point, the app keeps the changes made until then.
+    pub fn cancel(&mut self) {
+        if let Some(cancel) = self.cancel.take() {
+            let _ = cancel.send(());
+        }
+    }
+
+    pub fn shows_spinner(&self) -> bool {
+        self.started.elapsed() >= SPINNER_DELAY
+    }
+
+    pub async fn finished(&mut self) -> Finished {
+        self.future.as_mut().await
+    }
+}
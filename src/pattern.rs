@@ -0,0 +1,148 @@
+use clap::ValueEnum;
+use core::fmt;
+use regex::Regex;
+use std::ops::Range;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum PatternKind {
+    #[default]
+    Substring,
+    Glob,
+    Regex,
+}
+
+impl PatternKind {
+    pub fn next(self) -> Self {
+        match self {
+            PatternKind::Substring => PatternKind::Glob,
+            PatternKind::Glob => PatternKind::Regex,
+            PatternKind::Regex => PatternKind::Substring,
+        }
+    }
+}
+
+impl fmt::Display for PatternKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            PatternKind::Substring => "substring",
+            PatternKind::Glob => "glob",
+            PatternKind::Regex => "regex",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Compiled pattern matched against node names or lines of node data.
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    Substring(String),
+    Regex(Regex),
+}
+
+/// Translates `*` and `?` wildcards into an anchored regex.
+fn glob_to_regex(glob: &str) -> String {
+    let mut regex = String::from("^");
+    for c in glob.chars() {
+        match c {
+            '*' => regex.push_str(".*"),
+            '?' => regex.push('.'),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    regex
+}
+
+impl Pattern {
+    pub fn new(kind: PatternKind, pattern: &str) -> Result<Self, String> {
+        let regex = match kind {
+            PatternKind::Substring => return Ok(Pattern::Substring(pattern.to_owned())),
+            PatternKind::Glob => glob_to_regex(pattern),
+            PatternKind::Regex => pattern.to_owned(),
+        };
+        Regex::new(&regex)
+            .map(Pattern::Regex)
+            .map_err(|e| format!("Invalid {kind} pattern {pattern} : {e}"))
+    }
+
+    pub fn is_match(&self, text: &str) -> bool {
+        match self {
+            Pattern::Substring(pattern) => text.contains(pattern.as_str()),
+            Pattern::Regex(regex) => regex.is_match(text),
+        }
+    }
+
+    /// Byte range of the first match in `text`.
+    pub fn find(&self, text: &str) -> Option<Range<usize>> {
+        match self {
+            Pattern::Substring(pattern) => text
+                .find(pattern.as_str())
+                .map(|start| start..start + pattern.len()),
+            Pattern::Regex(regex) => regex.find(text).map(|found| found.range()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(kind: PatternKind, pattern: &str, text: &str) -> bool {
+        Pattern::new(kind, pattern).unwrap().is_match(text)
+    }
+
+    #[test]
+    fn translates_wildcards_and_escapes_metacharacters() {
+        assert_eq!(glob_to_regex("app-*.?"), r"^app\-.*\..$");
+        assert_eq!(glob_to_regex("(a)+[b]|c$"), r"^\(a\)\+\[b\]\|c\$$");
+        assert_eq!(glob_to_regex(""), "^$");
+    }
+
+    #[test]
+    fn glob_matches_whole_text() {
+        assert!(matches(PatternKind::Glob, "app-*", "app-config"));
+        assert!(matches(PatternKind::Glob, "node-?", "node-1"));
+        assert!(!matches(PatternKind::Glob, "node-?", "node-10"));
+        assert!(!matches(PatternKind::Glob, "config", "app-config"));
+        assert!(matches(PatternKind::Glob, "v1.0", "v1.0"));
+        assert!(!matches(PatternKind::Glob, "v1.0", "v1x0"));
+        assert!(matches(PatternKind::Glob, "a+b", "a+b"));
+    }
+
+    #[test]
+    fn substring_is_literal() {
+        assert!(matches(PatternKind::Substring, "o.n", "zoo.node"));
+        assert!(!matches(PatternKind::Substring, "o.n", "zoo_node"));
+        assert!(matches(PatternKind::Substring, "", "anything"));
+    }
+
+    #[test]
+    fn regex_is_unanchored() {
+        assert!(matches(
+            PatternKind::Regex,
+            r"node-\d+",
+            "the node-42 entry"
+        ));
+        assert!(!matches(PatternKind::Regex, r"^node-\d+$", "node-x"));
+    }
+
+    #[test]
+    fn finds_first_match_range() {
+        let find = |kind, pattern, text| Pattern::new(kind, pattern).unwrap().find(text);
+        assert_eq!(
+            find(PatternKind::Substring, "node", "a node, node"),
+            Some(2..6)
+        );
+        assert_eq!(find(PatternKind::Regex, r"\d+", "node-42"), Some(5..7));
+        assert_eq!(find(PatternKind::Glob, "app-*", "app-config"), Some(0..10));
+        assert_eq!(find(PatternKind::Substring, "x", "node"), None);
+    }
+
+    #[test]
+    fn rejects_invalid_regex() {
+        let e = Pattern::new(PatternKind::Regex, "(unclosed").unwrap_err();
+        assert!(e.starts_with("Invalid regex pattern (unclosed : "), "{e}");
+        // the same text is a valid glob since metacharacters are escaped
+        assert!(Pattern::new(PatternKind::Glob, "(unclosed").is_ok());
+    }
+}
@@ -0,0 +1,234 @@
+use crate::{
+    acl::{parse_perms, perms_to_string},
+    app::recursive_delete::walk_subtree,
+    errors::AppResult,
+    zk_path::ZkPath,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{fs, path::Path};
+use zookeeper_async::{Acl, Stat, ZkError, ZooKeeper};
+
+/// Portable copy of a subtree: every node with its data, stat and ACL, parents first.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct Snapshot {
+    pub root: String,
+    pub nodes: Vec<SnapshotNode>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SnapshotNode {
+    pub path: String,
+    pub data: SnapshotData,
+    pub stat: SnapshotStat,
+    pub acl: Vec<SnapshotAcl>,
+}
+
+/// Node data encoded the same way it is shown as `NodeData`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "encoding", content = "value", rename_all = "lowercase")]
+pub enum SnapshotData {
+    Utf8(String),
+    Json(Value),
+    Base64(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SnapshotStat {
+    pub czxid: i64,
+    pub mzxid: i64,
+    pub ctime: i64,
+    pub mtime: i64,
+    pub version: i32,
+    pub cversion: i32,
+    pub aversion: i32,
+    pub ephemeral_owner: i64,
+    pub data_length: i32,
+    pub num_children: i32,
+    pub pzxid: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SnapshotAcl {
+    pub scheme: String,
+    pub id: String,
+    pub perms: String, // cdrwa, '-' for missing permissions
+}
+
+impl SnapshotData {
+    /// Picks JSON only when it serializes back to the same bytes, so the data round-trips exactly.
+    pub fn encode(data: &[u8]) -> Self {
+        match std::str::from_utf8(data) {
+            Ok(string) => match serde_json::from_str::<Value>(string) {
+                Ok(value @ (Value::Object(_) | Value::Array(_)))
+                    if serde_json::to_vec(&value).is_ok_and(|json| json == data) =>
+                {
+                    SnapshotData::Json(value)
+                }
+                _ => SnapshotData::Utf8(string.to_owned()),
+            },
+            Err(_) => SnapshotData::Base64(STANDARD.encode(data)),
+        }
+    }
+
+    pub fn decode(&self) -> Result<Vec<u8>, String> {
+        match self {
+            SnapshotData::Utf8(string) => Ok(string.as_bytes().to_vec()),
+            SnapshotData::Json(value) => serde_json::to_vec(value).map_err(|e| e.to_string()),
+            SnapshotData::Base64(encoded) => STANDARD.decode(encoded).map_err(|e| e.to_string()),
+        }
+    }
+}
+
+impl From<&Stat> for SnapshotStat {
+    fn from(stat: &Stat) -> Self {
+        Self {
+            czxid: stat.czxid,
+            mzxid: stat.mzxid,
+            ctime: stat.ctime,
+            mtime: stat.mtime,
+            version: stat.version,
+            cversion: stat.cversion,
+            aversion: stat.aversion,
+            ephemeral_owner: stat.ephemeral_owner,
+            data_length: stat.data_length,
+            num_children: stat.num_children,
+            pzxid: stat.pzxid,
+        }
+    }
+}
+
+impl From<&Acl> for SnapshotAcl {
+    fn from(acl: &Acl) -> Self {
+        Self {
+            scheme: acl.scheme.clone(),
+            id: acl.id.clone(),
+            perms: perms_to_string(acl.perms),
+        }
+    }
+}
+
+impl SnapshotAcl {
+    pub fn to_acl(&self) -> Result<Acl, String> {
+        let perms = parse_perms(&self.perms.replace('-', ""))
+            .ok_or_else(|| format!("Invalid ACL permissions {}", self.perms))?;
+        Ok(Acl::new(perms, self.scheme.as_str(), self.id.as_str()))
+    }
+}
+
+/// `.yaml` and `.yml` files are written as YAML, anything else as JSON.
+fn is_yaml(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("yaml") || ext.eq_ignore_ascii_case("yml"))
+}
+
+impl Snapshot {
+    /// Reads `root` and all its descendants, failing on the first node that can't be read.
+    /// Nodes deleted while the subtree is read are left out.
+    pub async fn take(zk: &ZooKeeper, root: &ZkPath) -> Result<Self, (ZkPath, ZkError)> {
+        let mut nodes = Vec::new();
+        for path in walk_subtree(zk, root).await? {
+            let (data, stat) = match zk.get_data(&path, false).await {
+                Ok(res) => res,
+                Err(ZkError::NoNode) => continue,
+                Err(e) => return Err((path, e)),
+            };
+            let acl = match zk.get_acl(&path).await {
+                Ok((acl, _)) => acl,
+                Err(ZkError::NoNode) => continue,
+                Err(e) => return Err((path, e)),
+            };
+            nodes.push(SnapshotNode {
+                data: SnapshotData::encode(&data),
+                stat: SnapshotStat::from(&stat),
+                acl: acl.iter().map(SnapshotAcl::from).collect(),
+                path: path.to_string(),
+            });
+        }
+        Ok(Self {
+            root: root.to_string(),
+            nodes,
+        })
+    }
+
+    pub fn to_text(&self, yaml: bool) -> AppResult<String> {
+        if yaml {
+            Ok(serde_yaml::to_string(self)?)
+        } else {
+            Ok(serde_json::to_string_pretty(self)?)
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> AppResult<()> {
+        fs::write(path, self.to_text(is_yaml(path))?)?;
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> AppResult<Self> {
+        let content = fs::read_to_string(path)?;
+        if is_yaml(path) {
+            Ok(serde_yaml::from_str(&content)?)
+        } else {
+            Ok(serde_json::from_str(&content)?)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn round_trip(data: &[u8]) -> SnapshotData {
+        let encoded = SnapshotData::encode(data);
+        assert_eq!(encoded.decode().unwrap(), data);
+        encoded
+    }
+
+    #[test]
+    fn encodes_text_as_utf8() {
+        assert_eq!(round_trip(b""), SnapshotData::Utf8(String::new()));
+        assert_eq!(
+            round_trip(b"plain text"),
+            SnapshotData::Utf8("plain text".to_owned())
+        );
+        assert_eq!(round_trip(b"42"), SnapshotData::Utf8("42".to_owned()));
+        // JSON that would not serialize back to the same bytes stays a string
+        assert_eq!(
+            round_trip(b"{ \"a\": 1 }"),
+            SnapshotData::Utf8("{ \"a\": 1 }".to_owned())
+        );
+    }
+
+    #[test]
+    fn encodes_compact_json_objects_and_arrays_as_json() {
+        assert_eq!(
+            round_trip(b"{\"a\":[1,\"b\"]}"),
+            SnapshotData::Json(json!({ "a": [1, "b"] }))
+        );
+        assert_eq!(
+            round_trip(b"[true,null]"),
+            SnapshotData::Json(json!([true, null]))
+        );
+    }
+
+    #[test]
+    fn encodes_binary_as_base64() {
+        assert_eq!(
+            round_trip(&[0xff, 0x00, 0xfe]),
+            SnapshotData::Base64("/wD+".to_owned())
+        );
+        assert!(SnapshotData::Base64("not base64!".to_owned())
+            .decode()
+            .is_err());
+    }
+
+    #[test]
+    fn serializes_encoding_tag() {
+        let data = SnapshotData::encode(b"{\"a\":1}");
+        let value = serde_json::to_value(&data).unwrap();
+        assert_eq!(value, json!({ "encoding": "json", "value": { "a": 1 } }));
+        assert_eq!(serde_json::from_value::<SnapshotData>(value).unwrap(), data);
+    }
+}
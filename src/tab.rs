@@ -1,25 +1,71 @@
 use crate::{
-    app::{state::TabState, BASE_RESOURCE},
+    acl::AclPreset,
+    app::{
+        cluster::Cluster,
+        history::History,
+        recursive_delete::DeletePlan,
+        search::{SearchKind, SearchResult},
+        state::TabState,
+        tree::TreeView,
+    },
+    create_mode::NodeCreateMode,
+    diff::SubtreeDiff,
+    import::{ImportPlan, ImportStrategy},
     node_data::NodeData,
+    pattern::PatternKind,
+    zk_path::ZkPath,
 };
 use ratatui::{
     style::{palette::tailwind, Color, Stylize},
     text::Line,
     widgets::ListState,
 };
-use zookeeper_async::Stat;
+use zookeeper_async::{Acl, Stat};
+
+pub const TAB_TITLE_SIZE: usize = 24;
+
 #[derive(Debug)]
 pub struct Tab {
+    pub name: Option<String>, // the title shows the node path when not named
+    pub cluster: usize,       // index in App::clusters
+    pub stale: bool,          // a watch reported a change while the tab was in the background
     pub tab_data: Vec<String>,
     pub list_state: ListState,
-    pub curr_resource: Option<String>, // selected node for current nest level
-    pub prev_resources: Vec<String>,   // prev resources: e.g. /zookeeper/config
+    pub node_path: ZkPath,    // selected node, the one node operations act on
+    pub listing_path: ZkPath, // node whose children are shown in tab_data
     pub current_node_stat: Option<Stat>,
+    pub current_node_acl: Option<Vec<Acl>>,
     pub message: String,
     pub node_data: NodeData,
     pub node_path_buf: String,
     pub node_data_buf: String,
     pub input_buf: String,
+    pub create_mode: NodeCreateMode,
+    pub ttl_buf: String,
+    pub acl_preset: AclPreset,
+    pub acl_credentials_buf: String,
+    pub acl_buf: Vec<Acl>,
+    pub acl_version: i32,
+    pub acl_list_state: ListState,
+    pub recursive_delete: bool,
+    pub delete_plan: Option<DeletePlan>,
+    pub import_strategy: ImportStrategy,
+    pub import_plan: Option<ImportPlan>,
+    pub diff_connection_buf: String, // connect string of the right subtree, empty for this one
+    pub diff_auth_buf: String,       // digest credentials for the right connection
+    pub diff: Option<SubtreeDiff>,
+    pub diff_scroll: u16,
+    pub tree_mode: bool,
+    pub tree: TreeView,
+    pub pattern_kind: PatternKind,
+    pub filter_buf: String,
+    pub unfiltered_data: Option<Vec<String>>, // all children while a filter is applied
+    pub search_root: ZkPath,
+    pub path_completions: Vec<String>, // children matching the go-to path input
+    pub history: History,
+    pub search_kind: SearchKind,
+    pub search_results: Vec<SearchResult>,
+    pub search_list_state: ListState,
     pub state: TabState,
     pub toggle_stats_auto_load: bool,
 }
@@ -27,16 +73,46 @@ pub struct Tab {
 impl Default for Tab {
     fn default() -> Self {
         Self {
+            name: Default::default(),
+            cluster: Default::default(),
+            stale: Default::default(),
             tab_data: Default::default(),
             list_state: ListState::default().with_selected(Some(0usize)),
-            curr_resource: Some(BASE_RESOURCE.to_string()),
-            prev_resources: Default::default(),
+            node_path: Default::default(),
+            listing_path: Default::default(),
             current_node_stat: Default::default(),
+            current_node_acl: Default::default(),
             message: Default::default(),
             node_data: Default::default(),
             node_path_buf: Default::default(),
             node_data_buf: Default::default(),
             input_buf: Default::default(),
+            create_mode: Default::default(),
+            ttl_buf: Default::default(),
+            acl_preset: Default::default(),
+            acl_credentials_buf: Default::default(),
+            acl_buf: Default::default(),
+            acl_version: Default::default(),
+            acl_list_state: ListState::default().with_selected(Some(0usize)),
+            recursive_delete: Default::default(),
+            delete_plan: Default::default(),
+            import_strategy: Default::default(),
+            import_plan: Default::default(),
+            diff_connection_buf: Default::default(),
+            diff_auth_buf: Default::default(),
+            diff: Default::default(),
+            diff_scroll: Default::default(),
+            tree_mode: Default::default(),
+            tree: Default::default(),
+            pattern_kind: Default::default(),
+            filter_buf: Default::default(),
+            unfiltered_data: Default::default(),
+            search_root: Default::default(),
+            search_kind: Default::default(),
+            path_completions: Default::default(),
+            history: Default::default(),
+            search_results: Default::default(),
+            search_list_state: Default::default(),
             state: Default::default(),
             toggle_stats_auto_load: true,
         }
@@ -44,14 +120,27 @@ impl Default for Tab {
 }
 
 impl Tab {
-    pub fn title(&self) -> Line<'static> {
-        "  Tab  "
-            .to_string()
+    /// `1: cluster /path`, coloured after the environment of the cluster.
+    pub fn title(&self, index: usize, cluster: &Cluster) -> Line<'static> {
+        let name = match self.name {
+            Some(ref name) => name.clone(),
+            None => self.path_title(),
+        };
+        format!("  {}: {} {name}  ", index + 1, cluster.name)
             .fg(tailwind::SLATE.c200)
-            .bg(tailwind::BLUE.c900)
+            .bg(cluster.color())
             .into()
     }
 
+    /// Current node path, cut from the left to `TAB_TITLE_SIZE` characters.
+    fn path_title(&self) -> String {
+        let path = self.node_path.as_str();
+        match path.char_indices().rev().nth(TAB_TITLE_SIZE - 1) {
+            Some((start, _)) if start > 0 => format!("…{}", &path[start..]),
+            _ => path.to_owned(),
+        }
+    }
+
     pub fn highlite_style() -> (Color, Color) {
         (Color::default(), tailwind::AMBER.c700)
     }
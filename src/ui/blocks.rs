@@ -6,6 +6,14 @@ use ratatui::{
 };
 
 use super::ui_handle::AppUi;
+use crate::{
+    app::{
+        action::Action,
+        keymap::{KeyContext, KeyMap},
+        search::SearchKind,
+    },
+    pattern::PatternKind,
+};
 
 impl AppUi {
     pub(crate) fn default_styled_block() -> Block<'static> {
@@ -27,20 +35,186 @@ impl AppUi {
             .title_alignment(Alignment::Center)
     }
 
-    pub(crate) fn nodes_block() -> Block<'static> {
+    pub(crate) fn acl_block() -> Block<'static> {
+        AppUi::default_styled_block()
+            .title("Node ACL")
+            .title_alignment(Alignment::Center)
+    }
+
+    pub(crate) fn acl_editor_block(keys: &KeyMap) -> Block<'static> {
+        AppUi::default_styled_block()
+            .title("Edit ACL")
+            .on_dark_gray()
+            .title_alignment(Alignment::Center)
+            .title_bottom(keys.hints(
+                KeyContext::AclEditor,
+                &[
+                    (Action::MoveUp, "move up"),
+                    (Action::MoveDown, "move down"),
+                    (Action::NewEntry, "add an entry"),
+                    (Action::Remove, "remove"),
+                ],
+            ))
+            .title_bottom(keys.hints(
+                KeyContext::AclEditor,
+                &[
+                    (Action::TogglePermission('c'), "toggle create"),
+                    (Action::TogglePermission('d'), "toggle delete"),
+                    (Action::TogglePermission('r'), "toggle read"),
+                    (Action::TogglePermission('w'), "toggle write"),
+                    (Action::TogglePermission('a'), "toggle admin"),
+                ],
+            ))
+            .title_bottom(keys.hints(
+                KeyContext::AclEditor,
+                &[(Action::Back, "cancel"), (Action::Confirm, "Save")],
+            ))
+    }
+
+    pub(crate) fn acl_entry_block(keys: &KeyMap) -> Block<'static> {
         AppUi::default_styled_block()
-            .title("Nodes")
+            .title("New Entry (scheme:id:perms)")
+            .on_blue()
+            .title_alignment(Alignment::Center)
+            .title_bottom(keys.hints(
+                KeyContext::Input,
+                &[(Action::Back, "cancel"), (Action::Confirm, "Add")],
+            ))
+    }
+
+    pub(crate) fn nodes_block(filter: Option<String>, keys: &KeyMap) -> Block<'static> {
+        let block = AppUi::default_styled_block()
+            .title(filter.map_or("Nodes".to_owned(), |filter| format!("Nodes [{filter}]")))
+            .title_alignment(Alignment::Left)
+            .title_bottom(keys.hints(
+                KeyContext::Nodes,
+                &[
+                    (Action::Quit, "quit"),
+                    (Action::MoveUp, "Up"),
+                    (Action::MoveDown, "Down"),
+                    (Action::Confirm, "dir Down"),
+                    (Action::Back, "dir Up"),
+                    (Action::CreateNode, "Create Node"),
+                    (Action::DeleteNode, "Delete Node"),
+                ],
+            ))
+            .title_bottom(keys.hints(
+                KeyContext::Nodes,
+                &[
+                    (Action::ReadData, "Read data"),
+                    (Action::ToggleStats, "Stat auto-load"),
+                    (Action::EditAcl, "edit ACL"),
+                    (Action::ToggleTree, "Tree view"),
+                    (Action::Filter, "filter"),
+                    (Action::SearchNames, "search"),
+                    (Action::SearchData, "Grep data"),
+                    (Action::GoToPath, "go to path"),
+                ],
+            ));
+        AppUi::nodes_common_hints(block, keys)
+    }
+
+    pub(crate) fn nodes_filter_block(filter: String, keys: &KeyMap) -> Block<'static> {
+        AppUi::default_styled_block()
+            .title(format!("Nodes [{filter}]"))
+            .title_alignment(Alignment::Left)
+            .on_dark_gray()
+            .title_bottom(keys.hints(
+                KeyContext::Input,
+                &[
+                    (Action::Back, "clear"),
+                    (Action::Confirm, "keep the filter"),
+                    (Action::NextField, "change the pattern kind"),
+                    (Action::MoveUp, "Up"),
+                    (Action::MoveDown, "Down"),
+                ],
+            ))
+    }
+
+    pub(crate) fn nodes_tree_block(keys: &KeyMap) -> Block<'static> {
+        let block = AppUi::default_styled_block()
+            .title("Nodes (tree)")
             .title_alignment(Alignment::Left)
-            .title_bottom("(q)uit | ↑ to Up | ↓ to Down | Enter to dir Down | Esc to dir Up | (C)reate Node | (D)elete Node")
-            .title_bottom("(S)tat auto-load")
+            .title_bottom(keys.hints(
+                KeyContext::Nodes,
+                &[
+                    (Action::Quit, "quit"),
+                    (Action::MoveUp, "Up"),
+                    (Action::MoveDown, "Down"),
+                    (Action::Confirm, "expand/collapse"),
+                    (Action::Back, "collapse"),
+                    (Action::CreateNode, "Create Node"),
+                    (Action::DeleteNode, "Delete Node"),
+                ],
+            ))
+            .title_bottom(keys.hints(
+                KeyContext::Nodes,
+                &[
+                    (Action::ReadData, "Read data"),
+                    (Action::ToggleStats, "Stat auto-load"),
+                    (Action::EditAcl, "edit ACL"),
+                    (Action::ToggleTree, "flat list"),
+                    (Action::SearchNames, "search"),
+                    (Action::SearchData, "Grep data"),
+                    (Action::GoToPath, "go to path"),
+                ],
+            ));
+        AppUi::nodes_common_hints(block, keys)
+    }
+
+    /// Hints shared by the flat list and the tree of nodes.
+    fn nodes_common_hints<'a>(block: Block<'a>, keys: &KeyMap) -> Block<'a> {
+        block
+            .title_bottom(keys.hints(
+                KeyContext::Nodes,
+                &[
+                    (Action::HistoryBack, "Back"),
+                    (Action::HistoryForward, "Forward"),
+                    (Action::AddBookmark, "Bookmark node"),
+                    (Action::Bookmarks, "bookmarks"),
+                    (Action::Export, "export"),
+                    (Action::Import, "import"),
+                    (Action::Diff, "diff"),
+                ],
+            ))
+            .title_bottom(keys.hints(
+                KeyContext::Nodes,
+                &[
+                    (Action::NewTab, "New tab"),
+                    (Action::DuplicateTab, "Duplicate tab"),
+                    (Action::CloseTab, "Close tab"),
+                    (Action::RenameTab, "Name tab"),
+                    (Action::PreviousTab, "previous tab"),
+                    (Action::NextTab, "next tab"),
+                    (Action::Select(0), "switch tabs"),
+                ],
+            ))
+            .title_bottom(keys.hints(
+                KeyContext::Nodes,
+                &[
+                    (Action::OpenClusters, "open a cluster"),
+                    (Action::ToggleSplit, "split"),
+                    (Action::FocusOtherPane, "focus the other pane"),
+                    (Action::CopyToOtherPane, "copy to the other pane"),
+                ],
+            ))
     }
 
-    pub(crate) fn node_data_block() -> Block<'static> {
+    pub(crate) fn node_data_block(keys: &KeyMap) -> Block<'static> {
         AppUi::default_styled_block()
             .title("Node Data")
             .on_dark_gray()
             .title_alignment(Alignment::Center)
-            .title_bottom("ESC to cancel | (J)son | (S)tring | (R)aw | (E)dit")
+            .title_bottom(keys.hints(
+                KeyContext::NodeData,
+                &[
+                    (Action::Back, "cancel"),
+                    (Action::ShowJson, "Json"),
+                    (Action::ShowString, "String"),
+                    (Action::ShowRaw, "Raw"),
+                    (Action::EditData, "Edit"),
+                ],
+            ))
     }
 
     pub(crate) fn edit_path_active_block() -> Block<'static> {
@@ -57,20 +231,104 @@ impl AppUi {
             .title_alignment(Alignment::Center)
     }
 
-    pub(crate) fn edit_data_non_active_block() -> Block<'static> {
+    pub(crate) fn edit_data_non_active_block(keys: &KeyMap) -> Block<'static> {
         AppUi::default_styled_block()
             .title("Edit Data")
             .on_light_blue()
             .title_alignment(Alignment::Center)
-            .title_bottom("ESC to cancel | Enter to Create | Tab to Change Window")
+            .title_bottom(keys.hints(
+                KeyContext::Input,
+                &[
+                    (Action::Back, "cancel"),
+                    (Action::Confirm, "Create"),
+                    (Action::NextField, "Change Window"),
+                ],
+            ))
     }
 
-    pub(crate) fn edit_data_active_block() -> Block<'static> {
+    pub(crate) fn edit_data_active_block(keys: &KeyMap) -> Block<'static> {
         AppUi::default_styled_block()
             .title("Edit Data")
             .on_blue()
             .title_alignment(Alignment::Center)
-            .title_bottom("ESC to cancel | Enter to Create | Tab to Change Window")
+            .title_bottom(keys.hints(
+                KeyContext::Input,
+                &[
+                    (Action::Back, "cancel"),
+                    (Action::Confirm, "Create"),
+                    (Action::NextField, "Change Window"),
+                ],
+            ))
+    }
+
+    pub(crate) fn create_mode_active_block(keys: &KeyMap) -> Block<'static> {
+        AppUi::default_styled_block()
+            .title("Create Mode")
+            .on_blue()
+            .title_alignment(Alignment::Center)
+            .title_bottom(keys.hints(
+                KeyContext::Selector,
+                &[
+                    (Action::MoveUp, "previous mode"),
+                    (Action::MoveDown, "next mode"),
+                ],
+            ))
+    }
+
+    pub(crate) fn create_mode_non_active_block() -> Block<'static> {
+        AppUi::default_styled_block()
+            .title("Create Mode")
+            .on_light_blue()
+            .title_alignment(Alignment::Center)
+    }
+
+    pub(crate) fn ttl_active_block() -> Block<'static> {
+        AppUi::default_styled_block()
+            .title("TTL (ms)")
+            .on_blue()
+            .title_alignment(Alignment::Center)
+    }
+
+    pub(crate) fn ttl_non_active_block() -> Block<'static> {
+        AppUi::default_styled_block()
+            .title("TTL (ms)")
+            .on_light_blue()
+            .title_alignment(Alignment::Center)
+    }
+
+    pub(crate) fn acl_preset_active_block(keys: &KeyMap) -> Block<'static> {
+        AppUi::default_styled_block()
+            .title("ACL")
+            .on_blue()
+            .title_alignment(Alignment::Center)
+            .title_bottom(keys.hints(
+                KeyContext::Selector,
+                &[
+                    (Action::MoveUp, "previous preset"),
+                    (Action::MoveDown, "next preset"),
+                ],
+            ))
+    }
+
+    pub(crate) fn acl_preset_non_active_block() -> Block<'static> {
+        AppUi::default_styled_block()
+            .title("ACL")
+            .on_light_blue()
+            .title_alignment(Alignment::Center)
+    }
+
+    pub(crate) fn credentials_active_block() -> Block<'static> {
+        AppUi::default_styled_block()
+            .title("user:password")
+            .on_blue()
+            .title_alignment(Alignment::Center)
+    }
+
+    pub(crate) fn credentials_non_active_block() -> Block<'static> {
+        AppUi::default_styled_block()
+            .title("user:password")
+            .on_light_blue()
+            .title_alignment(Alignment::Center)
     }
 
     pub(crate) fn current_data_block() -> Block<'static> {
@@ -80,35 +338,256 @@ impl AppUi {
             .title_alignment(Alignment::Center)
     }
 
-    pub(crate) fn edited_data_block() -> Block<'static> {
+    pub(crate) fn edited_data_block(keys: &KeyMap) -> Block<'static> {
         AppUi::default_styled_block()
             .title("Edited Data")
             .on_dark_gray()
             .title_alignment(Alignment::Center)
-            .title_bottom("ESC to cancel | Enter to Save")
+            .title_bottom(keys.hints(
+                KeyContext::Input,
+                &[(Action::Back, "cancel"), (Action::Confirm, "Save")],
+            ))
     }
 
-    pub(crate) fn delete_node_block() -> Block<'static> {
+    pub(crate) fn delete_node_block(keys: &KeyMap) -> Block<'static> {
         AppUi::default_styled_block()
             .title("Node to Delete")
             .on_dark_gray()
             .title_alignment(Alignment::Center)
-            .title_bottom("Esc to cancel | Enter to Delete")
+            .title_bottom(keys.hints(
+                KeyContext::Input,
+                &[
+                    (Action::Back, "cancel"),
+                    (Action::Confirm, "Delete"),
+                    (Action::NextField, "toggle Recursive"),
+                ],
+            ))
     }
 
-    pub(crate) fn confirm_delete_block() -> Block<'static> {
+    pub(crate) fn recursive_delete_node_block(keys: &KeyMap) -> Block<'static> {
+        AppUi::default_styled_block()
+            .title("Node to Delete (Recursive)")
+            .on_dark_gray()
+            .title_alignment(Alignment::Center)
+            .title_bottom(keys.hints(
+                KeyContext::Input,
+                &[
+                    (Action::Back, "cancel"),
+                    (Action::Confirm, "Preview"),
+                    (Action::NextField, "toggle Recursive"),
+                ],
+            ))
+    }
+
+    pub(crate) fn deleting_nodes_block(keys: &KeyMap) -> Block<'static> {
+        AppUi::default_styled_block()
+            .title("Deleting Nodes")
+            .on_red()
+            .title_alignment(Alignment::Center)
+            .title_bottom(keys.hints(KeyContext::Deleting, &[(Action::Back, "cancel")]))
+    }
+
+    pub(crate) fn export_block(root: &str, keys: &KeyMap) -> Block<'static> {
+        AppUi::default_styled_block()
+            .title(format!("Export {root} to file (.json, .yaml)"))
+            .on_dark_gray()
+            .title_alignment(Alignment::Center)
+            .title_bottom(keys.hints(
+                KeyContext::Input,
+                &[(Action::Back, "cancel"), (Action::Confirm, "Export")],
+            ))
+    }
+
+    pub(crate) fn import_block(target: &str, keys: &KeyMap) -> Block<'static> {
+        AppUi::default_styled_block()
+            .title(format!("Import snapshot into {target}"))
+            .on_dark_gray()
+            .title_alignment(Alignment::Center)
+            .title_bottom(keys.hints(
+                KeyContext::Input,
+                &[
+                    (Action::Back, "cancel"),
+                    (Action::Confirm, "Preview"),
+                    (Action::NextField, "change Strategy"),
+                ],
+            ))
+    }
+
+    pub(crate) fn import_preview_block(
+        title: &'static str,
+        has_conflicts: bool,
+        keys: &KeyMap,
+    ) -> Block<'static> {
+        let block = AppUi::default_styled_block()
+            .title(title)
+            .title_alignment(Alignment::Center);
+        if has_conflicts {
+            block
+                .on_red()
+                .title_bottom(keys.hints(KeyContext::Preview, &[(Action::Back, "close")]))
+        } else {
+            block.on_dark_gray().title_bottom(keys.hints(
+                KeyContext::Preview,
+                &[(Action::Back, "cancel"), (Action::Confirm, "Import")],
+            ))
+        }
+    }
+
+    pub(crate) fn diff_popup_block(keys: &KeyMap) -> Block<'static> {
+        AppUi::default_styled_block()
+            .title("Compare Subtrees")
+            .on_dark_gray()
+            .title_alignment(Alignment::Center)
+            .title_bottom(keys.hints(
+                KeyContext::Input,
+                &[
+                    (Action::Back, "cancel"),
+                    (Action::Confirm, "Compare"),
+                    (Action::NextField, "switch field"),
+                ],
+            ))
+    }
+
+    pub(crate) fn diff_input_block(title: &'static str) -> Block<'static> {
+        AppUi::default_styled_block().title(title)
+    }
+
+    pub(crate) fn diff_block(summary: String, keys: &KeyMap) -> Block<'static> {
+        AppUi::default_styled_block()
+            .title(summary)
+            .title_alignment(Alignment::Center)
+            .title_bottom(keys.hints(
+                KeyContext::List,
+                &[
+                    (Action::MoveDown, "scroll down"),
+                    (Action::MoveUp, "scroll up"),
+                    (Action::Back, "edit paths"),
+                    (Action::Close, "close"),
+                ],
+            ))
+    }
+
+    pub(crate) fn search_block(
+        search_kind: SearchKind,
+        root: &str,
+        kind: PatternKind,
+        keys: &KeyMap,
+    ) -> Block<'static> {
+        AppUi::default_styled_block()
+            .title(format!("Search {search_kind} under {root} ({kind})"))
+            .on_dark_gray()
+            .title_alignment(Alignment::Center)
+            .title_bottom(keys.hints(
+                KeyContext::Input,
+                &[
+                    (Action::Back, "cancel"),
+                    (Action::Confirm, "Search"),
+                    (Action::NextField, "change the pattern kind"),
+                ],
+            ))
+    }
+
+    pub(crate) fn go_to_path_block(keys: &KeyMap) -> Block<'static> {
+        AppUi::default_styled_block()
+            .title("Go to path")
+            .on_dark_gray()
+            .title_alignment(Alignment::Center)
+            .title_bottom(keys.hints(
+                KeyContext::Input,
+                &[
+                    (Action::Back, "cancel"),
+                    (Action::Confirm, "Go"),
+                    (Action::NextField, "complete"),
+                ],
+            ))
+    }
+
+    pub(crate) fn bookmark_name_block(path: &str, keys: &KeyMap) -> Block<'static> {
+        AppUi::default_styled_block()
+            .title(format!("Bookmark {path} as"))
+            .on_dark_gray()
+            .title_alignment(Alignment::Center)
+            .title_bottom(keys.hints(
+                KeyContext::Input,
+                &[(Action::Back, "cancel"), (Action::Confirm, "Save")],
+            ))
+    }
+
+    pub(crate) fn rename_tab_block(keys: &KeyMap) -> Block<'static> {
+        AppUi::default_styled_block()
+            .title("Rename tab (empty to show the node path)")
+            .on_dark_gray()
+            .title_alignment(Alignment::Center)
+            .title_bottom(keys.hints(
+                KeyContext::Input,
+                &[(Action::Back, "cancel"), (Action::Confirm, "Rename")],
+            ))
+    }
+
+    pub(crate) fn bookmarks_block(keys: &KeyMap) -> Block<'static> {
+        AppUi::default_styled_block()
+            .title("Bookmarks")
+            .title_alignment(Alignment::Center)
+            .title_bottom(keys.hints(
+                KeyContext::List,
+                &[
+                    (Action::MoveUp, "move up"),
+                    (Action::MoveDown, "move down"),
+                    (Action::Confirm, "open"),
+                    (Action::Select(0), "open by number"),
+                    (Action::Remove, "delete"),
+                    (Action::Back, "close"),
+                ],
+            ))
+    }
+
+    pub(crate) fn clusters_block(keys: &KeyMap) -> Block<'static> {
+        AppUi::default_styled_block()
+            .title("Open a tab on")
+            .title_alignment(Alignment::Center)
+            .title_bottom(keys.hints(
+                KeyContext::List,
+                &[
+                    (Action::MoveUp, "move up"),
+                    (Action::MoveDown, "move down"),
+                    (Action::Confirm, "open"),
+                    (Action::Back, "close"),
+                ],
+            ))
+    }
+
+    pub(crate) fn search_results_block(message: String, keys: &KeyMap) -> Block<'static> {
+        AppUi::default_styled_block()
+            .title(message)
+            .title_alignment(Alignment::Center)
+            .title_bottom(keys.hints(
+                KeyContext::List,
+                &[
+                    (Action::MoveUp, "move up"),
+                    (Action::MoveDown, "move down"),
+                    (Action::Confirm, "open"),
+                    (Action::Back, "edit the search"),
+                    (Action::Close, "close"),
+                ],
+            ))
+    }
+
+    pub(crate) fn confirm_delete_block(keys: &KeyMap) -> Block<'static> {
         AppUi::default_styled_block()
             .title("Confirm Delete")
             .on_red()
             .title_alignment(Alignment::Center)
-            .title_bottom("Esc to cancel | Enter to Delete")
+            .title_bottom(keys.hints(
+                KeyContext::Input,
+                &[(Action::Back, "cancel"), (Action::Confirm, "Delete")],
+            ))
     }
 
-    pub(crate) fn connection_editing_block() -> Block<'static> {
+    pub(crate) fn connection_editing_block(keys: &KeyMap) -> Block<'static> {
         AppUi::default_styled_block()
             .title("Connect")
-            .title_bottom("ESC to cancel")
-            .title_bottom("ENTER to save")
+            .title_bottom(keys.hints(KeyContext::Input, &[(Action::Back, "cancel")]))
+            .title_bottom(keys.hints(KeyContext::Input, &[(Action::Confirm, "save")]))
             .title_alignment(Alignment::Center)
     }
     pub(crate) fn connection_input_block() -> Block<'static> {
@@ -118,23 +597,82 @@ impl AppUi {
             .on_dark_gray()
     }
 
-    pub(crate) fn connection_frame_block() -> Block<'static> {
+    pub(crate) fn connection_input_active_block() -> Block<'static> {
+        AppUi::connection_input_block().on_blue()
+    }
+
+    pub(crate) fn auth_input_block() -> Block<'static> {
+        AppUi::connection_input_block().title("Digest auth (user:password)")
+    }
+
+    pub(crate) fn auth_input_active_block() -> Block<'static> {
+        AppUi::auth_input_block().on_blue()
+    }
+
+    pub(crate) fn connection_failure_block(keys: &KeyMap) -> Block<'static> {
+        AppUi::default_styled_block()
+            .title("Connection Failed")
+            .on_red()
+            .title_alignment(Alignment::Center)
+            .title_bottom(keys.hints(KeyContext::ConnectionFailed, &[(Action::Back, "cancel")]))
+            .title_bottom(keys.hints(KeyContext::ConnectionFailed, &[(Action::Confirm, "retry")]))
+            .title_bottom(keys.hints(
+                KeyContext::ConnectionFailed,
+                &[(Action::EditConnection, "edit")],
+            ))
+            .title_bottom(keys.hints(
+                KeyContext::ConnectionFailed,
+                &[(Action::EditAuth, "edit auth")],
+            ))
+    }
+
+    pub(crate) fn profiles_block(keys: &KeyMap) -> Block<'static> {
+        Block::default()
+            .title("Profiles")
+            .borders(Borders::ALL)
+            .border_set(symbols::border::PLAIN)
+            .title_bottom(keys.hints(KeyContext::Connection, &[(Action::MoveDown, "select next")]))
+            .title_bottom(keys.hints(
+                KeyContext::Connection,
+                &[(Action::MoveUp, "select previous")],
+            ))
+            .title_bottom(keys.hints(KeyContext::Connection, &[(Action::SaveProfile, "save")]))
+            .title_bottom(keys.hints(KeyContext::Connection, &[(Action::Remove, "delete")]))
+            .title_alignment(Alignment::Center)
+    }
+
+    pub(crate) fn profile_name_input_block(keys: &KeyMap) -> Block<'static> {
+        AppUi::connection_input_active_block()
+            .title("Profile name")
+            .title_bottom(keys.hints(KeyContext::Input, &[(Action::Back, "cancel")]))
+            .title_bottom(keys.hints(KeyContext::Input, &[(Action::Confirm, "save")]))
+    }
+
+    pub(crate) fn confirm_delete_profile_block(keys: &KeyMap) -> Block<'static> {
+        AppUi::connection_input_block()
+            .on_red()
+            .title("Delete profile")
+            .title_bottom(keys.hints(KeyContext::Preview, &[(Action::Back, "cancel")]))
+            .title_bottom(keys.hints(KeyContext::Preview, &[(Action::Confirm, "delete")]))
+    }
+
+    pub(crate) fn connection_frame_block(keys: &KeyMap) -> Block<'static> {
         Block::default()
             .title("zui.rs")
             .borders(Borders::ALL)
             .border_set(symbols::border::ONE_EIGHTH_WIDE)
-            .title_bottom("q to quit")
+            .title_bottom(keys.hints(KeyContext::Connection, &[(Action::Quit, "quit")]))
     }
 
-    pub(crate) fn connection_popup_block() -> Block<'static> {
+    pub(crate) fn connection_popup_block(keys: &KeyMap) -> Block<'static> {
         Block::default()
             .title("Connect")
             .borders(Borders::ALL)
             .border_set(symbols::border::THICK)
             .on_gray()
-            .title_bottom("ESC to cancel")
-            .title_bottom("ENTER to connect")
-            .title_bottom("e to edit")
+            .title_bottom(keys.hints(KeyContext::Connection, &[(Action::Confirm, "connect")]))
+            .title_bottom(keys.hints(KeyContext::Connection, &[(Action::EditConnection, "edit")]))
+            .title_bottom(keys.hints(KeyContext::Connection, &[(Action::EditAuth, "edit auth")]))
             .title_alignment(Alignment::Center)
     }
 }
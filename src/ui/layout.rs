@@ -1,6 +1,7 @@
-use ratatui::layout::{Constraint, Layout};
+use ratatui::layout::{Constraint, Layout, Rect};
 
 use super::ui_handle::AppUi;
+use crate::app::split::SplitOrientation;
 
 impl AppUi {
     pub(crate) fn tab_screen_layout() -> Layout {
@@ -10,6 +11,7 @@ impl AppUi {
                 Constraint::Fill(1),
                 Constraint::Percentage(85),
                 Constraint::Fill(5),
+                Constraint::Length(1),
             ],
         )
     }
@@ -21,6 +23,14 @@ impl AppUi {
         )
     }
 
+    pub(crate) fn split_layout(orientation: SplitOrientation) -> Layout {
+        let panes = vec![Constraint::Fill(1), Constraint::Fill(1)];
+        match orientation {
+            SplitOrientation::Vertical => Layout::horizontal(panes),
+            SplitOrientation::Horizontal => Layout::vertical(panes),
+        }
+    }
+
     pub(crate) fn vertical_equal_layout() -> Layout {
         Layout::vertical(vec![
             Constraint::Fill(1),
@@ -46,4 +56,53 @@ impl AppUi {
     pub(crate) fn vertical_double_popup_layout() -> Layout {
         Layout::vertical(vec![Constraint::Fill(1), Constraint::Fill(1)])
     }
+
+    pub(crate) fn create_node_popup_layout() -> Layout {
+        Layout::vertical(vec![
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Length(3),
+            Constraint::Length(3),
+        ])
+    }
+
+    pub(crate) fn diff_popup_layout() -> Layout {
+        Layout::vertical(vec![
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Fill(1),
+        ])
+    }
+
+    pub(crate) fn node_info_layout() -> Layout {
+        Layout::vertical(vec![Constraint::Fill(2), Constraint::Fill(1)])
+    }
+
+    pub(crate) fn create_mode_row_layout() -> Layout {
+        Layout::horizontal(vec![Constraint::Fill(2), Constraint::Fill(1)])
+    }
+
+    pub(crate) fn recursive_delete_popup_layout() -> Layout {
+        Layout::vertical(vec![Constraint::Fill(1), Constraint::Length(3)])
+    }
+
+    pub(crate) fn status_bar_layout(session_width: u16) -> Layout {
+        Layout::horizontal(vec![Constraint::Length(session_width), Constraint::Fill(1)])
+    }
+
+    /// Right end of the bottom line.
+    pub(crate) fn operation_status_rect(area: Rect, width: u16) -> Rect {
+        let [_, status_row] =
+            Layout::vertical(vec![Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+        let [_, status_rect] =
+            Layout::horizontal(vec![Constraint::Fill(1), Constraint::Length(width)])
+                .areas(status_row);
+        status_rect
+    }
+
+    pub(crate) fn tabs_bar_layout(mode_width: u16) -> Layout {
+        Layout::horizontal(vec![Constraint::Fill(1), Constraint::Length(mode_width)])
+    }
 }
@@ -23,4 +23,39 @@ impl AppUi {
             .split(data_popup_rect)[1],
         )[1]
     }
+
+    pub(crate) fn connection_popup_rect(frame_rect: Rect) -> Rect {
+        AppUi::horizontal_equal_layout().split(AppUi::vertical_equal_layout().split(frame_rect)[1])
+            [1]
+    }
+
+    pub(crate) fn profiles_rect(frame_rect: Rect) -> Rect {
+        AppUi::horizontal_equal_layout().split(AppUi::vertical_equal_layout().split(frame_rect)[0])
+            [1]
+    }
+
+    pub(crate) fn profile_name_input_rect(profiles_rect: Rect) -> Rect {
+        let [_, input_rect] =
+            Layout::vertical(vec![Constraint::Fill(1), Constraint::Length(3)]).areas(profiles_rect);
+        input_rect
+    }
+
+    pub(crate) fn connection_inputs_rects(connection_popup_rect: Rect) -> [Rect; 3] {
+        let [_, inputs_rect, _] = Layout::horizontal(vec![
+            Constraint::Fill(1),
+            Constraint::Fill(5),
+            Constraint::Fill(1),
+        ])
+        .areas(connection_popup_rect);
+
+        let [_, connection_rect, auth_rect, error_rect, _] = Layout::vertical(vec![
+            Constraint::Fill(1),
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(2),
+            Constraint::Fill(1),
+        ])
+        .areas(inputs_rect);
+        [connection_rect, auth_rect, error_rect]
+    }
 }
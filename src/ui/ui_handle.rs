@@ -1,16 +1,23 @@
-use std::vec;
-
 use ratatui::{
-    layout::{Constraint, Layout},
-    widgets::Paragraph,
+    buffer::Buffer,
+    layout::Rect,
+    style::{palette::tailwind, Stylize},
+    text::Line,
+    widgets::{Clear, Paragraph, Widget, Wrap},
     Frame,
 };
-
-use crate::app::{
-    state::{AppState, TabState},
-    App,
+use std::time::Duration;
+
+use crate::{
+    app::{
+        state::{AppState, TabState},
+        App,
+    },
+    events::TICK_INTERVAL,
 };
 
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
 pub struct AppUi {}
 
 impl AppUi {
@@ -19,98 +26,194 @@ impl AppUi {
             AppState::EstablishingConnection => {
                 AppUi::render_connection_screen(frame, app);
             }
-            AppState::EditingConnection => {
+            AppState::ConnectionFailed => {
+                AppUi::render_connection_screen(frame, app);
+                AppUi::render_connection_failure(frame, app);
+            }
+            AppState::EditingProfileName => {
+                AppUi::render_connection_screen(frame, app);
+                AppUi::render_profile_name_input(frame, app);
+            }
+            AppState::ConfirmDeleteProfile => {
+                AppUi::render_connection_screen(frame, app);
+                AppUi::render_confirm_delete_profile(frame, app);
+            }
+            AppState::EditingConnection | AppState::EditingAuth => {
                 AppUi::render_connection_editing_screen(frame, app);
             }
             AppState::Tab => match app.curr_tab().state {
                 TabState::Tab => AppUi::render_tab_screen(frame, app),
                 TabState::ReadNodeData => AppUi::render_node_data_screen(frame, app),
-                TabState::EditCreateNodePath => {
-                    AppUi::render_edit_create_node_path_screen(frame, app)
-                }
-                TabState::EditCreateNodeData => {
-                    AppUi::render_edit_create_node_data_screen(frame, app)
+                TabState::EditCreateNodePath
+                | TabState::EditCreateNodeData
+                | TabState::EditCreateNodeMode
+                | TabState::EditCreateNodeTtl
+                | TabState::EditCreateNodeAcl
+                | TabState::EditCreateNodeCredentials => {
+                    AppUi::render_edit_create_node_screen(frame, app)
                 }
                 TabState::EditNodeData => AppUi::render_edit_node_data_screen(frame, app),
                 TabState::DeleteNode => AppUi::render_delete_node_screen(frame, app),
+                TabState::ConfirmDelete if app.curr_tab().recursive_delete => {
+                    AppUi::render_confirm_recursive_delete_screen(frame, app)
+                }
                 TabState::ConfirmDelete => AppUi::render_confirm_delete_screen(frame, app),
+                TabState::DeletingNodes => AppUi::render_deleting_nodes_screen(frame, app),
+                TabState::EditAcl | TabState::EditAclEntry => {
+                    AppUi::render_edit_acl_screen(frame, app)
+                }
+                TabState::ExportSubtree => AppUi::render_export_screen(frame, app),
+                TabState::ImportSnapshot => AppUi::render_import_screen(frame, app),
+                TabState::ImportPreview | TabState::CopyPreview => {
+                    AppUi::render_import_preview_screen(frame, app)
+                }
+                TabState::EditDiffLeft
+                | TabState::EditDiffRight
+                | TabState::EditDiffConnection
+                | TabState::EditDiffAuth => AppUi::render_edit_diff_screen(frame, app),
+                TabState::DiffView => AppUi::render_diff_screen(frame, app),
+                TabState::FilterNodes => AppUi::render_tab_screen(frame, app),
+                TabState::SearchInput => AppUi::render_search_input_screen(frame, app),
+                TabState::SearchResults => AppUi::render_search_results_screen(frame, app),
+                TabState::GoToPath => AppUi::render_go_to_path_screen(frame, app),
+                TabState::EditBookmarkName => AppUi::render_bookmark_name_screen(frame, app),
+                TabState::RenameTab => AppUi::render_rename_tab_screen(frame, app),
+                TabState::Bookmarks => AppUi::render_bookmarks_screen(frame, app),
+                TabState::Clusters => AppUi::render_clusters_screen(frame, app),
             },
         }
     }
 
     pub fn render_connection_editing_screen(frame: &mut Frame, app: &mut App) {
-        let frame_layout = AppUi::vertical_equal_layout().split(frame.area());
-
-        let popup_layout = AppUi::horizontal_equal_layout().split(frame_layout[1]);
-
-        let popup_block = AppUi::connection_editing_block();
-
-        let input_layout = Layout::horizontal(vec![
-            Constraint::Fill(1),
-            Constraint::Fill(5),
-            Constraint::Fill(1),
-        ])
-        .split(
-            Layout::vertical(vec![
-                Constraint::Fill(10),
-                Constraint::Fill(3),
-                Constraint::Fill(10),
-            ])
-            .split(popup_layout[1])[1],
-        );
+        let popup_rect = AppUi::connection_popup_rect(frame.area());
+        let [connection_rect, auth_rect, error_rect] = AppUi::connection_inputs_rects(popup_rect);
+
+        let (connection_block, auth_block) = match app.state {
+            AppState::EditingAuth => (
+                AppUi::connection_input_block(),
+                AppUi::auth_input_active_block(),
+            ),
+            _ => (
+                AppUi::connection_input_active_block(),
+                AppUi::auth_input_block(),
+            ),
+        };
 
-        let popup_input_block = AppUi::connection_input_block();
+        let connection_string =
+            Paragraph::new(app.connection_input.as_str()).block(connection_block);
+        let auth = Paragraph::new(app.masked_auth_input()).block(auth_block);
+
+        frame.render_widget(AppUi::connection_frame_block(&app.keymap), frame.area());
+        frame.render_widget(AppUi::connection_editing_block(&app.keymap), popup_rect);
+        frame.render_widget(connection_string, connection_rect);
+        frame.render_widget(auth, auth_rect);
+        AppUi::render_connection_error(frame, app, error_rect);
+    }
+    pub fn render_connection_screen(frame: &mut Frame, app: &mut App) {
+        let popup_rect = AppUi::connection_popup_rect(frame.area());
+        let [connection_rect, auth_rect, error_rect] = AppUi::connection_inputs_rects(popup_rect);
 
         let connection_string =
-            Paragraph::new(app.connection_input.as_str()).block(popup_input_block);
+            Paragraph::new(app.connection_input.as_str()).block(AppUi::connection_input_block());
+        let auth = Paragraph::new(app.masked_auth_input()).block(AppUi::auth_input_block());
+
+        frame.render_widget(AppUi::connection_frame_block(&app.keymap), frame.area());
+        frame.render_widget(AppUi::connection_popup_block(&app.keymap), popup_rect);
+        frame.render_widget(connection_string, connection_rect);
+        frame.render_widget(auth, auth_rect);
+        AppUi::render_connection_error(frame, app, error_rect);
+        app.render_profiles_list(AppUi::profiles_rect(frame.area()), frame.buffer_mut());
+    }
 
-        let frame_block = AppUi::connection_frame_block();
+    fn render_connection_failure(frame: &mut Frame, app: &App) {
+        let popup_rect = AppUi::connection_popup_rect(frame.area());
+        let failure = format!(
+            "Attempt {} to connect to {} failed:\n\n{}",
+            app.connection_attempts,
+            app.connection_str(),
+            app.connection_failure.as_deref().unwrap_or_default()
+        );
+        let failure = Paragraph::new(failure)
+            .wrap(Wrap { trim: true })
+            .block(AppUi::connection_failure_block(&app.keymap));
 
-        frame.render_widget(frame_block, frame.area());
-        frame.render_widget(popup_block, popup_layout[1]);
-        frame.render_widget(connection_string, input_layout[1]);
+        frame.render_widget(Clear, popup_rect);
+        frame.render_widget(failure, popup_rect);
     }
-    pub fn render_connection_screen(frame: &mut Frame, app: &mut App) {
-        let frame_layout = AppUi::vertical_equal_layout().split(frame.area());
-
-        let popup_layout = AppUi::horizontal_equal_layout().split(frame_layout[1]);
-
-        let popup_block = AppUi::connection_popup_block();
-
-        let input_layout = Layout::horizontal(vec![
-            Constraint::Fill(1),
-            Constraint::Fill(5),
-            Constraint::Fill(1),
-        ])
-        .split(
-            Layout::vertical(vec![
-                Constraint::Fill(10),
-                Constraint::Fill(3),
-                Constraint::Fill(10),
-            ])
-            .split(popup_layout[1])[1],
-        );
 
-        let popup_input_block = AppUi::connection_input_block();
+    fn render_profile_name_input(frame: &mut Frame, app: &App) {
+        let input_rect = AppUi::profile_name_input_rect(AppUi::profiles_rect(frame.area()));
+        let input = Paragraph::new(app.profile_name_input.as_str())
+            .block(AppUi::profile_name_input_block(&app.keymap));
 
-        let connection_string =
-            Paragraph::new(app.connection_input.as_str()).block(popup_input_block);
+        frame.render_widget(Clear, input_rect);
+        frame.render_widget(input, input_rect);
+    }
 
-        let frame_block = AppUi::connection_frame_block();
+    fn render_confirm_delete_profile(frame: &mut Frame, app: &App) {
+        let confirm_rect = AppUi::profile_name_input_rect(AppUi::profiles_rect(frame.area()));
+        let name = app
+            .selected_profile()
+            .map(|profile| profile.name.as_str())
+            .unwrap_or_default();
+        let confirm = Paragraph::new(format!("Delete profile {name}?"))
+            .block(AppUi::confirm_delete_profile_block(&app.keymap));
+
+        frame.render_widget(Clear, confirm_rect);
+        frame.render_widget(confirm, confirm_rect);
+    }
 
-        frame.render_widget(frame_block, frame.area());
-        frame.render_widget(popup_block, popup_layout[1]);
-        frame.render_widget(connection_string, input_layout[1]);
+    fn render_connection_error(frame: &mut Frame, app: &App, area: Rect) {
+        if let Some(ref error) = app.connection_error {
+            let error = Paragraph::new(error.as_str())
+                .wrap(Wrap { trim: true })
+                .red();
+            frame.render_widget(error, area);
+        }
     }
 
-    pub fn render_tab_screen(frame: &mut Frame, app: &mut App) {
-        let [tabs_rect, work_rect, msg_rect] = AppUi::tab_screen_layout().areas(frame.area());
-        let [nodes_list_rect, node_stat_rect] = AppUi::work_space_layout().areas(work_rect);
+    /// Shows the last frame of the app with a spinner while an operation owns the app.
+    pub fn render_operation(frame: &mut Frame, last_frame: &Buffer, elapsed: Duration) {
+        let area = frame.area().intersection(last_frame.area);
+        let buf = frame.buffer_mut();
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                buf[(x, y)] = last_frame[(x, y)].clone();
+            }
+        }
+
+        let frame_index = elapsed.as_millis() / TICK_INTERVAL.as_millis();
+        let spinner = SPINNER[frame_index as usize % SPINNER.len()];
+        let status = Line::from(format!(
+            " {spinner} Waiting for Zookeeper {:.1}s, Esc to cancel ",
+            elapsed.as_secs_f32()
+        ))
+        .fg(tailwind::SLATE.c200)
+        .bg(tailwind::INDIGO.c700);
+        let status_rect = AppUi::operation_status_rect(frame.area(), status.width() as u16);
+        status.render(status_rect, frame.buffer_mut());
+    }
 
-        app.render_node_stat(node_stat_rect, frame.buffer_mut());
+    pub fn render_tab_screen(frame: &mut Frame, app: &mut App) {
+        let [tabs_rect, work_rect, msg_rect, status_rect] =
+            AppUi::tab_screen_layout().areas(frame.area());
+        match app.split {
+            Some(split) => {
+                let [first_rect, second_rect] =
+                    AppUi::split_layout(split.orientation).areas(work_rect);
+                let (focused_rect, other_rect) = if split.focus_first {
+                    (first_rect, second_rect)
+                } else {
+                    (second_rect, first_rect)
+                };
+                app.render_work_space(focused_rect, frame.buffer_mut());
+                app.render_other_pane(other_rect, frame.buffer_mut());
+            }
+            None => app.render_work_space(work_rect, frame.buffer_mut()),
+        }
         app.render_tabs(tabs_rect, frame.buffer_mut());
-        app.render_nodes_list(nodes_list_rect, frame.buffer_mut());
         app.render_message_block(msg_rect, frame.buffer_mut());
+        app.render_status_bar(status_rect, frame.buffer_mut());
     }
 
     pub fn render_node_data_screen(frame: &mut Frame, app: &mut App) {
@@ -121,31 +224,82 @@ impl AppUi {
         app.render_node_data(data_popup_rect, frame.buffer_mut());
     }
 
-    pub fn render_edit_create_node_path_screen(frame: &mut Frame, app: &mut App) {
+    pub fn render_edit_create_node_screen(frame: &mut Frame, app: &mut App) {
         AppUi::render_tab_screen(frame, app);
         let work_rect = AppUi::tab_screen_layout().split(frame.area())[1];
 
         let data_popup_rect = AppUi::horizontal_equal_layout()
             .split(AppUi::vertical_doubled_layout().split(work_rect)[1])[1];
 
-        let [edit_path_rect, edit_data_rect] =
-            AppUi::vertical_double_popup_layout().areas(data_popup_rect);
+        let [edit_path_rect, edit_data_rect, edit_mode_rect, edit_acl_rect] =
+            AppUi::create_node_popup_layout().areas(data_popup_rect);
 
-        app.render_edit_path_active_block(edit_path_rect, frame.buffer_mut());
-        app.render_edit_data_non_active_block(edit_data_rect, frame.buffer_mut());
+        match app.curr_tab().state {
+            TabState::EditCreateNodePath => {
+                app.render_edit_path_active_block(edit_path_rect, frame.buffer_mut())
+            }
+            _ => app.render_edit_path_non_active_block(edit_path_rect, frame.buffer_mut()),
+        }
+        match app.curr_tab().state {
+            TabState::EditCreateNodeData => {
+                app.render_edit_data_active_block(edit_data_rect, frame.buffer_mut())
+            }
+            _ => app.render_edit_data_non_active_block(edit_data_rect, frame.buffer_mut()),
+        }
+
+        AppUi::render_create_node_acl_row(frame, app, edit_acl_rect);
+
+        if !app.curr_tab().create_mode.requires_ttl() {
+            match app.curr_tab().state {
+                TabState::EditCreateNodeMode => {
+                    app.render_create_mode_active_block(edit_mode_rect, frame.buffer_mut())
+                }
+                _ => app.render_create_mode_non_active_block(edit_mode_rect, frame.buffer_mut()),
+            }
+            return;
+        }
+
+        let [mode_rect, ttl_rect] = AppUi::create_mode_row_layout().areas(edit_mode_rect);
+        match app.curr_tab().state {
+            TabState::EditCreateNodeMode => {
+                app.render_create_mode_active_block(mode_rect, frame.buffer_mut())
+            }
+            _ => app.render_create_mode_non_active_block(mode_rect, frame.buffer_mut()),
+        }
+        match app.curr_tab().state {
+            TabState::EditCreateNodeTtl => {
+                app.render_ttl_active_block(ttl_rect, frame.buffer_mut())
+            }
+            _ => app.render_ttl_non_active_block(ttl_rect, frame.buffer_mut()),
+        }
     }
-    pub fn render_edit_create_node_data_screen(frame: &mut Frame, app: &mut App) {
-        AppUi::render_tab_screen(frame, app);
-        let work_rect = AppUi::tab_screen_layout().split(frame.area())[1];
 
-        let data_popup_rect = AppUi::horizontal_equal_layout()
-            .split(AppUi::vertical_doubled_layout().split(work_rect)[1])[1];
+    fn render_create_node_acl_row(frame: &mut Frame, app: &mut App, area: Rect) {
+        if !app.curr_tab().acl_preset.requires_credentials() {
+            match app.curr_tab().state {
+                TabState::EditCreateNodeAcl => {
+                    app.render_acl_preset_active_block(area, frame.buffer_mut())
+                }
+                _ => app.render_acl_preset_non_active_block(area, frame.buffer_mut()),
+            }
+            return;
+        }
 
-        let [edit_path_rect, edit_data_rect] =
-            AppUi::vertical_double_popup_layout().areas(data_popup_rect);
-        app.render_edit_path_non_active_block(edit_path_rect, frame.buffer_mut());
-        app.render_edit_data_active_block(edit_data_rect, frame.buffer_mut());
+        let [preset_rect, credentials_rect] = AppUi::create_mode_row_layout().areas(area);
+        match app.curr_tab().state {
+            TabState::EditCreateNodeAcl => {
+                app.render_acl_preset_active_block(preset_rect, frame.buffer_mut())
+            }
+            _ => app.render_acl_preset_non_active_block(preset_rect, frame.buffer_mut()),
+        }
+        match app.curr_tab().state {
+            TabState::EditCreateNodeCredentials => {
+                app.render_credentials_active_block(credentials_rect, frame.buffer_mut())
+            }
+            _ => app.render_credentials_non_active_block(credentials_rect, frame.buffer_mut()),
+        }
     }
+
     pub fn render_edit_node_data_screen(frame: &mut Frame, app: &mut App) {
         AppUi::render_tab_screen(frame, app);
         let work_rect = AppUi::tab_screen_layout().split(frame.area())[1];
@@ -173,4 +327,118 @@ impl AppUi {
         let data_popup_rect = AppUi::data_popup_rect(work_rect);
         app.render_confirm_delete_node(data_popup_rect, frame.buffer_mut())
     }
+
+    fn render_confirm_recursive_delete_screen(frame: &mut Frame, app: &mut App) {
+        AppUi::render_tab_screen(frame, app);
+        let work_rect = AppUi::tab_screen_layout().split(frame.area())[1];
+        let data_popup_rect = AppUi::horizontal_equal_layout()
+            .split(AppUi::vertical_doubled_layout().split(work_rect)[1])[1];
+        app.render_confirm_recursive_delete_node(data_popup_rect, frame.buffer_mut())
+    }
+
+    fn render_deleting_nodes_screen(frame: &mut Frame, app: &mut App) {
+        AppUi::render_tab_screen(frame, app);
+        let work_rect = AppUi::tab_screen_layout().split(frame.area())[1];
+        let data_popup_rect = AppUi::data_popup_rect(work_rect);
+        app.render_deleting_nodes(data_popup_rect, frame.buffer_mut())
+    }
+
+    fn render_export_screen(frame: &mut Frame, app: &mut App) {
+        AppUi::render_tab_screen(frame, app);
+        let work_rect = AppUi::tab_screen_layout().split(frame.area())[1];
+        let data_popup_rect = AppUi::data_popup_rect(work_rect);
+        app.render_export(data_popup_rect, frame.buffer_mut())
+    }
+
+    fn render_import_screen(frame: &mut Frame, app: &mut App) {
+        AppUi::render_tab_screen(frame, app);
+        let work_rect = AppUi::tab_screen_layout().split(frame.area())[1];
+        let data_popup_rect = AppUi::data_popup_rect(work_rect);
+        app.render_import(data_popup_rect, frame.buffer_mut())
+    }
+
+    fn render_import_preview_screen(frame: &mut Frame, app: &mut App) {
+        AppUi::render_tab_screen(frame, app);
+        let work_rect = AppUi::tab_screen_layout().split(frame.area())[1];
+        let data_popup_rect = AppUi::horizontal_equal_layout()
+            .split(AppUi::vertical_doubled_layout().split(work_rect)[1])[1];
+        app.render_import_preview(data_popup_rect, frame.buffer_mut())
+    }
+
+    fn render_edit_diff_screen(frame: &mut Frame, app: &mut App) {
+        AppUi::render_tab_screen(frame, app);
+        let work_rect = AppUi::tab_screen_layout().split(frame.area())[1];
+        let data_popup_rect = AppUi::data_popup_rect(work_rect);
+        app.render_edit_diff(data_popup_rect, frame.buffer_mut())
+    }
+
+    fn render_diff_screen(frame: &mut Frame, app: &mut App) {
+        AppUi::render_tab_screen(frame, app);
+        let work_rect = AppUi::tab_screen_layout().split(frame.area())[1];
+        app.render_diff(work_rect, frame.buffer_mut())
+    }
+
+    fn render_search_input_screen(frame: &mut Frame, app: &mut App) {
+        AppUi::render_tab_screen(frame, app);
+        let work_rect = AppUi::tab_screen_layout().split(frame.area())[1];
+        let data_popup_rect = AppUi::data_popup_rect(work_rect);
+        app.render_search_input(data_popup_rect, frame.buffer_mut())
+    }
+
+    fn render_search_results_screen(frame: &mut Frame, app: &mut App) {
+        AppUi::render_tab_screen(frame, app);
+        let work_rect = AppUi::tab_screen_layout().split(frame.area())[1];
+        app.render_search_results(work_rect, frame.buffer_mut())
+    }
+
+    fn render_go_to_path_screen(frame: &mut Frame, app: &mut App) {
+        AppUi::render_tab_screen(frame, app);
+        let work_rect = AppUi::tab_screen_layout().split(frame.area())[1];
+        let data_popup_rect = AppUi::horizontal_equal_layout()
+            .split(AppUi::vertical_doubled_layout().split(work_rect)[1])[1];
+        app.render_go_to_path(data_popup_rect, frame.buffer_mut())
+    }
+
+    fn render_bookmark_name_screen(frame: &mut Frame, app: &mut App) {
+        AppUi::render_tab_screen(frame, app);
+        let work_rect = AppUi::tab_screen_layout().split(frame.area())[1];
+        let data_popup_rect = AppUi::data_popup_rect(work_rect);
+        app.render_bookmark_name(data_popup_rect, frame.buffer_mut())
+    }
+
+    fn render_rename_tab_screen(frame: &mut Frame, app: &mut App) {
+        AppUi::render_tab_screen(frame, app);
+        let work_rect = AppUi::tab_screen_layout().split(frame.area())[1];
+        let data_popup_rect = AppUi::data_popup_rect(work_rect);
+        app.render_rename_tab(data_popup_rect, frame.buffer_mut())
+    }
+
+    fn render_bookmarks_screen(frame: &mut Frame, app: &mut App) {
+        AppUi::render_tab_screen(frame, app);
+        let work_rect = AppUi::tab_screen_layout().split(frame.area())[1];
+        let data_popup_rect = AppUi::horizontal_equal_layout()
+            .split(AppUi::vertical_doubled_layout().split(work_rect)[1])[1];
+        app.render_bookmarks(data_popup_rect, frame.buffer_mut())
+    }
+
+    fn render_clusters_screen(frame: &mut Frame, app: &mut App) {
+        AppUi::render_tab_screen(frame, app);
+        let work_rect = AppUi::tab_screen_layout().split(frame.area())[1];
+        let data_popup_rect = AppUi::horizontal_equal_layout()
+            .split(AppUi::vertical_doubled_layout().split(work_rect)[1])[1];
+        app.render_clusters(data_popup_rect, frame.buffer_mut())
+    }
+
+    fn render_edit_acl_screen(frame: &mut Frame, app: &mut App) {
+        AppUi::render_tab_screen(frame, app);
+        let work_rect = AppUi::tab_screen_layout().split(frame.area())[1];
+        let data_popup_rect = AppUi::horizontal_equal_layout()
+            .split(AppUi::vertical_doubled_layout().split(work_rect)[1])[1];
+        app.render_acl_editor(data_popup_rect, frame.buffer_mut());
+
+        if app.curr_tab().state == TabState::EditAclEntry {
+            let [_, entry_rect] = AppUi::recursive_delete_popup_layout().areas(data_popup_rect);
+            app.render_acl_entry_input(entry_rect, frame.buffer_mut());
+        }
+    }
 }
@@ -1,8 +1,44 @@
-use zookeeper_async::{WatchedEvent, Watcher};
+use crate::events::{AppEvent, EventSender};
+use zookeeper_async::WatchedEvent;
 
-pub struct LoggingWatcher;
-impl Watcher for LoggingWatcher {
-    fn handle(&self, e: WatchedEvent) {
-        println!("{:?}", e)
+/// Forwards zookeeper watch and session events of one cluster to the application event loop.
+#[derive(Default)]
+pub struct WatchChannel {
+    cluster: usize,
+    generation: u64, // bumped on reconnect to drop events of the previous session
+    tx: Option<EventSender>, // events are dropped when there is no event loop
+}
+
+impl WatchChannel {
+    pub fn new(cluster: usize, tx: Option<EventSender>) -> Self {
+        Self {
+            cluster,
+            generation: 0,
+            tx,
+        }
+    }
+
+    pub fn watcher(&self) -> impl Fn(WatchedEvent) + Send + 'static {
+        let tx = self.tx.clone();
+        let cluster = self.cluster;
+        let generation = self.generation;
+        move |event| {
+            if let Some(ref tx) = tx {
+                let _ = tx.send(AppEvent::Watch {
+                    cluster,
+                    generation,
+                    event,
+                });
+            }
+        }
+    }
+
+    /// Ignores events from watchers created before this call.
+    pub fn renew(&mut self) {
+        self.generation += 1;
+    }
+
+    pub fn is_current(&self, generation: u64) -> bool {
+        generation == self.generation
     }
 }
@@ -0,0 +1,196 @@
+use crate::errors::AppError;
+use serde::Serialize;
+use std::{fmt, ops::Deref, str::FromStr};
+
+const SEPARATOR: char = '/';
+
+/// Absolute Zookeeper node path, without a trailing `/` except for the root itself.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+pub struct ZkPath(String);
+
+impl ZkPath {
+    pub fn root() -> Self {
+        Self(SEPARATOR.to_string())
+    }
+
+    /// Validates `path` the same way the Zookeeper server does.
+    pub fn parse(path: &str) -> Result<Self, AppError> {
+        if path == "/" {
+            return Ok(Self::root());
+        }
+        let Some(relative) = path.strip_prefix(SEPARATOR) else {
+            return Err(invalid(path, "must start with /"));
+        };
+        if path.ends_with(SEPARATOR) {
+            return Err(invalid(path, "must not end with /"));
+        }
+        for segment in relative.split(SEPARATOR) {
+            check_name(path, segment)?;
+        }
+        Ok(Self(path.to_owned()))
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.0.len() == 1
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Appends the node `name`, checked like every node name of `parse`.
+    pub fn join(&self, name: &str) -> Result<Self, AppError> {
+        let path = if self.is_root() {
+            format!("/{name}")
+        } else {
+            format!("{}/{name}", self.0)
+        };
+        if name.contains(SEPARATOR) {
+            return Err(invalid(&path, "node name must not contain /"));
+        }
+        check_name(&path, name)?;
+        Ok(Self(path))
+    }
+
+    pub fn parent(&self) -> Option<Self> {
+        match self.0.rsplit_once(SEPARATOR)? {
+            ("", "") => None,
+            ("", _) => Some(Self::root()),
+            (parent, _) => Some(Self(parent.to_owned())),
+        }
+    }
+
+    /// Last node name, empty for the root.
+    pub fn basename(&self) -> &str {
+        self.0.rsplit(SEPARATOR).next().unwrap_or_default()
+    }
+
+    /// Node names from the root down, empty for the root.
+    pub fn segments(&self) -> impl Iterator<Item = &str> {
+        self.0
+            .split(SEPARATOR)
+            .filter(|segment| !segment.is_empty())
+    }
+
+    pub fn depth(&self) -> usize {
+        self.segments().count()
+    }
+
+    /// The root followed by every ancestor of the path down to the path itself.
+    pub fn ancestors(&self) -> impl Iterator<Item = ZkPath> + '_ {
+        let root = Self::root();
+        std::iter::once(root.clone()).chain(self.segments().scan(root, |path, segment| {
+            *path = path.join(segment).ok()?;
+            Some(path.clone())
+        }))
+    }
+}
+
+fn check_name(path: &str, name: &str) -> Result<(), AppError> {
+    match name {
+        "" => Err(invalid(path, "empty node name")),
+        "." | ".." => Err(invalid(path, "relative paths are not allowed")),
+        _ if name.chars().any(char::is_control) => {
+            Err(invalid(path, "control characters are not allowed"))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn invalid(path: &str, reason: &str) -> AppError {
+    AppError::InvalidPath(path.to_owned(), reason.to_owned())
+}
+
+impl Default for ZkPath {
+    fn default() -> Self {
+        Self::root()
+    }
+}
+
+impl FromStr for ZkPath {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl Deref for ZkPath {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for ZkPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(path: &str) -> ZkPath {
+        ZkPath::parse(path).unwrap()
+    }
+
+    #[test]
+    fn parses_valid_paths() {
+        assert!(path("/").is_root());
+        assert_eq!(path("/app/config").as_str(), "/app/config");
+        assert_eq!(path("/a.b/..c/...").as_str(), "/a.b/..c/...");
+        assert_eq!(path("/zoo keeper/ünïcode").as_str(), "/zoo keeper/ünïcode");
+    }
+
+    #[test]
+    fn rejects_invalid_paths() {
+        for invalid in [
+            "",
+            "app",
+            "/app/",
+            "//",
+            "/app//config",
+            "/.",
+            "/app/..",
+            "/app/./config",
+            "/app\nconfig",
+            "/app/\u{7f}",
+        ] {
+            assert!(ZkPath::parse(invalid).is_err(), "{invalid:?}");
+        }
+    }
+
+    #[test]
+    fn joins_checked_names() {
+        assert_eq!(ZkPath::root().join("app").unwrap(), path("/app"));
+        assert_eq!(path("/app").join("config").unwrap(), path("/app/config"));
+        for name in ["", ".", "..", "a/b", "a//b", "/a", "a\tb"] {
+            assert!(path("/app").join(name).is_err(), "{name:?}");
+        }
+    }
+
+    #[test]
+    fn splits_parent_and_basename() {
+        assert_eq!(ZkPath::root().parent(), None);
+        assert_eq!(ZkPath::root().basename(), "");
+        assert_eq!(path("/app").parent(), Some(ZkPath::root()));
+        assert_eq!(path("/app/config").parent(), Some(path("/app")));
+        assert_eq!(path("/app/config").basename(), "config");
+        assert_eq!(path("/app/config").depth(), 2);
+    }
+
+    #[test]
+    fn lists_ancestors_from_root() {
+        assert_eq!(
+            ZkPath::root().ancestors().collect::<Vec<_>>(),
+            [ZkPath::root()]
+        );
+        assert_eq!(
+            path("/a/b/c").ancestors().collect::<Vec<_>>(),
+            [path("/"), path("/a"), path("/a/b"), path("/a/b/c")]
+        );
+    }
+}